@@ -0,0 +1,211 @@
+use crate::pattern;
+use proc_macro::{Delimiter, TokenStream, TokenTree};
+
+/// Fields of a struct or an enum variant.
+pub enum Fields {
+    Named(Vec<String>),
+    Tuple(usize),
+    Unit,
+}
+
+/// A struct or an enum variant with the pattern of its
+/// `#[aoc(pattern = "...")]` attribute.
+pub struct Variant {
+    pub name: String,
+    pub fields: Fields,
+    pub pattern: Option<String>,
+}
+
+pub enum Data {
+    Struct(Variant),
+    Enum(Vec<Variant>),
+}
+
+/// The type the derive is applied to.
+pub struct Item {
+    pub name: String,
+    pub data: Data,
+}
+
+impl Item {
+    pub fn parse(input: TokenStream) -> Result<Self, String> {
+        let tokens: Vec<_> = input.into_iter().collect();
+        let mut tokens = tokens.as_slice();
+
+        let pattern = attributes(&mut tokens)?;
+        visibility(&mut tokens);
+        let keyword = ident(&mut tokens)?;
+        let name = ident(&mut tokens)?;
+        if is_punct(tokens.first(), '<') {
+            return Err("AocParse does not support generic types".into());
+        }
+
+        let data = match keyword.as_str() {
+            "struct" => Data::Struct(Variant {
+                name: name.clone(),
+                fields: fields(tokens.first())?,
+                pattern,
+            }),
+            "enum" => {
+                if pattern.is_some() {
+                    return Err("the patterns of an enum belong to its variants".into());
+                }
+                match tokens.first() {
+                    Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Brace => {
+                        Data::Enum(variants(g.stream())?)
+                    }
+                    _ => return Err(format!("expected the variants of {name}")),
+                }
+            }
+            _ => return Err("AocParse can only be derived for structs and enums".into()),
+        };
+
+        Ok(Self { name, data })
+    }
+}
+
+/// Consumes the attributes in front of an item, field or variant and
+/// returns the pattern of an `aoc` attribute.
+fn attributes(tokens: &mut &[TokenTree]) -> Result<Option<String>, String> {
+    let mut pattern = None;
+    while let [TokenTree::Punct(hash), TokenTree::Group(attr), rest @ ..] = *tokens {
+        if hash.as_char() != '#' || attr.delimiter() != Delimiter::Bracket {
+            break;
+        }
+        *tokens = rest;
+
+        let attr: Vec<_> = attr.stream().into_iter().collect();
+        let [TokenTree::Ident(name), args @ ..] = attr.as_slice() else {
+            continue;
+        };
+        if name.to_string() != "aoc" {
+            continue;
+        }
+        if pattern.is_some() {
+            return Err("duplicate aoc attribute".into());
+        }
+
+        let args: Vec<_> = match args {
+            [TokenTree::Group(g)] if g.delimiter() == Delimiter::Parenthesis => {
+                g.stream().into_iter().collect()
+            }
+            _ => return Err("expected #[aoc(pattern = \"...\")]".into()),
+        };
+        match args.as_slice() {
+            [
+                TokenTree::Ident(key),
+                TokenTree::Punct(eq),
+                TokenTree::Literal(lit),
+            ] if eq.as_char() == '=' => {
+                if key.to_string() != "pattern" {
+                    return Err(format!("unknown aoc attribute key '{key}'"));
+                }
+                pattern = Some(pattern::unescape(&lit.to_string())?);
+            }
+            _ => return Err("expected #[aoc(pattern = \"...\")]".into()),
+        }
+    }
+    Ok(pattern)
+}
+
+/// Consumes a visibility like `pub` or `pub(crate)`.
+fn visibility(tokens: &mut &[TokenTree]) {
+    if let [TokenTree::Ident(i), rest @ ..] = *tokens
+        && i.to_string() == "pub"
+    {
+        *tokens = match rest {
+            [TokenTree::Group(g), rest @ ..] if g.delimiter() == Delimiter::Parenthesis => rest,
+            rest => rest,
+        };
+    }
+}
+
+fn ident(tokens: &mut &[TokenTree]) -> Result<String, String> {
+    match *tokens {
+        [TokenTree::Ident(i), rest @ ..] => {
+            *tokens = rest;
+            Ok(i.to_string())
+        }
+        [t, ..] => Err(format!("expected an identifier, found '{t}'")),
+        [] => Err("expected an identifier".into()),
+    }
+}
+
+fn is_punct(token: Option<&TokenTree>, c: char) -> bool {
+    matches!(token, Some(TokenTree::Punct(p)) if p.as_char() == c)
+}
+
+/// Returns the fields declared by the group following a struct or variant
+/// name.
+fn fields(group: Option<&TokenTree>) -> Result<Fields, String> {
+    let Some(TokenTree::Group(group)) = group else {
+        return Ok(Fields::Unit);
+    };
+    match group.delimiter() {
+        Delimiter::Parenthesis => Ok(Fields::Tuple(split_commas(group.stream()).len())),
+        Delimiter::Brace => split_commas(group.stream())
+            .iter()
+            .map(|field| {
+                let mut field = field.as_slice();
+                attributes(&mut field)?;
+                visibility(&mut field);
+                ident(&mut field)
+            })
+            .collect::<Result<_, _>>()
+            .map(Fields::Named),
+        _ => Ok(Fields::Unit),
+    }
+}
+
+fn variants(stream: TokenStream) -> Result<Vec<Variant>, String> {
+    let variants = split_commas(stream)
+        .iter()
+        .map(|variant| {
+            let mut variant = variant.as_slice();
+            let pattern = attributes(&mut variant)?;
+            let name = ident(&mut variant)?;
+            let fields = fields(variant.first())?;
+            Ok(Variant {
+                name,
+                fields,
+                pattern,
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    if variants.is_empty() {
+        return Err("AocParse can not be derived for enums without variants".into());
+    }
+    Ok(variants)
+}
+
+/// Splits a list of fields or variants at the commas which are not part of
+/// a generic type like `HashMap<K, V>`.
+fn split_commas(stream: TokenStream) -> Vec<Vec<TokenTree>> {
+    let mut items = vec![vec![]];
+    let mut depth = 0usize;
+    let mut prev = None;
+
+    for token in stream {
+        if let TokenTree::Punct(p) = &token {
+            match p.as_char() {
+                ',' if depth == 0 => {
+                    items.push(vec![]);
+                    prev = None;
+                    continue;
+                }
+                '<' => depth += 1,
+                // Not the end of a generic in `-> T` of function types.
+                '>' if prev != Some('-') => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            prev = Some(p.as_char());
+        } else {
+            prev = None;
+        }
+        items.last_mut().unwrap().push(token);
+    }
+
+    items.retain(|item| !item.is_empty());
+    items
+}
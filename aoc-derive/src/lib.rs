@@ -0,0 +1,153 @@
+//! Derive macro generating `FromStr` implementations from record patterns,
+//! see `lib::record` for the pattern syntax and the runtime support of the
+//! generated code.
+
+mod item;
+mod pattern;
+
+use item::{Data, Fields, Item, Variant};
+use pattern::Piece;
+use proc_macro::TokenStream;
+
+/// Path of the runtime support of the generated code.
+const RECORD: &str = "::lib::record";
+
+/// Derives `FromStr` with `lib::record::RecordError` errors and
+/// `lib::record::Record` from the `#[aoc(pattern = "...")]` attribute of a
+/// struct or of each variant of an enum.
+#[proc_macro_derive(AocParse, attributes(aoc))]
+pub fn derive_aoc_parse(input: TokenStream) -> TokenStream {
+    let code = match expand(input) {
+        Ok(code) => code,
+        Err(err) => format!("::core::compile_error!({:?});", format!("AocParse: {err}")),
+    };
+    code.parse().expect("generated code is valid")
+}
+
+fn expand(input: TokenStream) -> Result<String, String> {
+    let item = Item::parse(input)?;
+
+    let body = match &item.data {
+        Data::Struct(variant) => format!(
+            "{} Ok({})",
+            parse_variant(variant)?,
+            constructor("Self", variant)
+        ),
+        Data::Enum(variants) => {
+            let mut body = String::new();
+            let mut prefixes = vec![];
+            for variant in variants {
+                let code = parse_variant(variant)?;
+                let prefix = match pieces(variant)?.first() {
+                    Some(Piece::Literal(prefix)) => prefix.clone(),
+                    _ => String::new(),
+                };
+                body += &format!(
+                    "if scanner.starts_with({prefix:?}) {{ {code} return Ok({}); }}",
+                    constructor(&format!("Self::{}", variant.name), variant)
+                );
+                prefixes.push(format!("{prefix:?}"));
+            }
+            body + &format!("Err(scanner.variant(&[{}]))", prefixes.join(", "))
+        }
+    };
+
+    Ok(format!(
+        "#[automatically_derived]
+        impl ::core::str::FromStr for {name} {{
+            type Err = {RECORD}::RecordError;
+
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {{
+                #[allow(unused_mut)]
+                let mut scanner = {RECORD}::__private::Scanner::new(s);
+                {body}
+            }}
+        }}
+
+        #[automatically_derived]
+        impl {RECORD}::Record for {name} {{}}",
+        name = item.name
+    ))
+}
+
+fn pieces(variant: &Variant) -> Result<Vec<Piece>, String> {
+    let pattern = variant.pattern.as_deref().ok_or_else(|| {
+        format!(
+            "{} needs an #[aoc(pattern = \"...\")] attribute",
+            variant.name
+        )
+    })?;
+    pattern::parse(pattern).map_err(|e| format!("{}: {e}", variant.name))
+}
+
+/// Returns the statements matching the pattern of a variant against the
+/// scanner and binding its fields to variables.
+fn parse_variant(variant: &Variant) -> Result<String, String> {
+    let pieces = pieces(variant)?;
+
+    let names: Vec<String> = match &variant.fields {
+        Fields::Named(names) => names.clone(),
+        Fields::Tuple(n) => (0..*n).map(|i| i.to_string()).collect(),
+        Fields::Unit => vec![],
+    };
+    let mut seen = vec![];
+    for piece in &pieces {
+        if let Piece::Field { name, .. } = piece {
+            if !names.contains(name) {
+                return Err(format!("{} has no field '{name}'", variant.name));
+            }
+            if seen.contains(&name) {
+                return Err(format!("field '{name}' appears twice in the pattern"));
+            }
+            seen.push(name);
+        }
+    }
+    if let Some(missing) = names.iter().find(|n| !seen.contains(n)) {
+        return Err(format!("field '{missing}' is missing in the pattern"));
+    }
+
+    let mut code = String::new();
+    for (i, piece) in pieces.iter().enumerate() {
+        match piece {
+            Piece::Literal(literal) => code += &format!("scanner.literal({literal:?})?;"),
+            Piece::Field { name, separators } => {
+                let end = match pieces.get(i + 1) {
+                    Some(Piece::Literal(end)) => format!("Some({end:?})"),
+                    _ => "None".into(),
+                };
+                let mut value = format!("{RECORD}::__private::field(value, at, {name:?})");
+                for separator in separators.iter().rev() {
+                    value = format!(
+                        "{RECORD}::__private::split(value, at, {separator:?}, |value, at| {value})"
+                    );
+                }
+                code += &format!(
+                    "let {var} = {{ let (value, at) = scanner.until({end})?; {value}? }};",
+                    var = var(name)
+                );
+            }
+        }
+    }
+    Ok(code + "scanner.end()?;")
+}
+
+/// Returns the expression constructing a variant at `path` from the
+/// variables bound by [`parse_variant`].
+fn constructor(path: &str, variant: &Variant) -> String {
+    match &variant.fields {
+        Fields::Named(names) => {
+            let fields: Vec<_> = names.iter().map(|n| format!("{n}: {}", var(n))).collect();
+            format!("{path} {{ {} }}", fields.join(", "))
+        }
+        Fields::Tuple(n) => {
+            let fields: Vec<_> = (0..*n).map(|i| var(&i.to_string())).collect();
+            format!("{path}({})", fields.join(", "))
+        }
+        Fields::Unit => path.into(),
+    }
+}
+
+/// Returns the variable the value of a field is bound to.
+fn var(field: &str) -> String {
+    format!("__aoc_{}", field.trim_start_matches("r#"))
+}
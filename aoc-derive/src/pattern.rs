@@ -0,0 +1,207 @@
+/// Part of a record pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Piece {
+    /// Text which has to appear literally.
+    Literal(String),
+    /// A placeholder for a field, split into a `Vec` at each separator,
+    /// nested from the outermost to the innermost one.
+    Field {
+        name: String,
+        separators: Vec<String>,
+    },
+}
+
+/// Parses a pattern like `{result}: {components: ' '}`.
+///
+/// Braces are escaped as `{{` and `}}`. Separators are given in single
+/// quotes and may not contain a quote themselves.
+pub fn parse(pattern: &str) -> Result<Vec<Piece>, String> {
+    let mut pieces = vec![];
+    let mut literal = String::new();
+    let mut chars = pattern.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '}' => return Err("unmatched '}' in pattern, use '}}' for a literal one".into()),
+            '{' => {
+                if !literal.is_empty() {
+                    pieces.push(Piece::Literal(std::mem::take(&mut literal)));
+                }
+                if let Some(Piece::Field { name, .. }) = pieces.last() {
+                    return Err(format!(
+                        "field '{name}' has to be followed by text which ends it"
+                    ));
+                }
+                pieces.push(field(&mut chars)?);
+            }
+            c => literal.push(c),
+        }
+    }
+
+    if !literal.is_empty() {
+        pieces.push(Piece::Literal(literal));
+    }
+    Ok(pieces)
+}
+
+/// Parses a placeholder after its opening brace up to the closing one.
+fn field(chars: &mut impl Iterator<Item = char>) -> Result<Piece, String> {
+    let mut name = String::new();
+    let mut separators = vec![];
+
+    let mut in_separators = false;
+    loop {
+        match chars.next() {
+            None => return Err("unclosed '{' in pattern".into()),
+            Some('}') => break,
+            Some(':') if !in_separators => in_separators = true,
+            Some(c) if !in_separators => name.push(c),
+            Some(c) if c.is_whitespace() => {}
+            Some('\'') => {
+                let separator: String = chars.by_ref().take_while(|&c| c != '\'').collect();
+                if separator.is_empty() {
+                    return Err(format!("empty separator for field '{}'", name.trim()));
+                }
+                separators.push(separator);
+            }
+            Some(c) => {
+                return Err(format!(
+                    "expected a quoted separator for field '{}', found '{c}'",
+                    name.trim()
+                ));
+            }
+        }
+    }
+
+    let name = name.trim().to_owned();
+    let valid = name
+        .strip_prefix("r#")
+        .unwrap_or(&name)
+        .chars()
+        .all(|c| c.is_alphanumeric() || c == '_');
+    if name.is_empty() || !valid {
+        return Err(format!("invalid field name '{name}' in pattern"));
+    }
+    Ok(Piece::Field { name, separators })
+}
+
+/// Returns the value of a string literal token like `"a\nb"` or `r#"a"#`.
+pub fn unescape(token: &str) -> Result<String, String> {
+    if let Some(raw) = token.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        return raw[hashes..]
+            .strip_suffix(&raw[..hashes])
+            .and_then(|s| s.strip_prefix('"')?.strip_suffix('"'))
+            .map(Into::into)
+            .ok_or_else(|| format!("expected a string literal, found {token}"));
+    }
+
+    let inner = token
+        .strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .ok_or_else(|| format!("expected a string literal, found {token}"))?;
+
+    let mut res = String::new();
+    let mut chars = inner.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            res.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => res.push('\n'),
+            Some('r') => res.push('\r'),
+            Some('t') => res.push('\t'),
+            Some('0') => res.push('\0'),
+            Some(c @ ('\\' | '\'' | '"')) => res.push(c),
+            Some('x') => {
+                let hex: String = chars.by_ref().take(2).collect();
+                let c = u8::from_str_radix(&hex, 16)
+                    .map_err(|_| format!("invalid escape '\\x{hex}'"))?;
+                res.push(c as char);
+            }
+            Some('u') => {
+                let hex: String = chars
+                    .by_ref()
+                    .skip(1)
+                    .take_while(|&c| c != '}')
+                    .filter(|&c| c != '_')
+                    .collect();
+                let c = u32::from_str_radix(&hex, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| format!("invalid escape '\\u{{{hex}}}'"))?;
+                res.push(c);
+            }
+            // A line continuation skips the line break and the indentation
+            // of the next line.
+            Some('\n') => while chars.next_if(|c| c.is_whitespace()).is_some() {},
+            c => return Err(format!("invalid escape '\\{}'", c.unwrap_or(' '))),
+        }
+    }
+    Ok(res)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn lit(s: &str) -> Piece {
+        Piece::Literal(s.into())
+    }
+
+    fn field(name: &str, separators: &[&str]) -> Piece {
+        Piece::Field {
+            name: name.into(),
+            separators: separators.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse("{result}: {components: ' '}"),
+            Ok(vec![
+                field("result", &[]),
+                lit(": "),
+                field("components", &[" "])
+            ])
+        );
+        assert_eq!(
+            parse("p={0:';' ','}}} {{x"),
+            Ok(vec![lit("p="), field("0", &[";", ","]), lit("} {x")])
+        );
+        assert_eq!(parse(""), Ok(vec![]));
+
+        assert!(parse("{a}{b}").is_err());
+        assert!(parse("{a").is_err());
+        assert!(parse("a}").is_err());
+        assert!(parse("{}").is_err());
+        assert!(parse("{a: ,}").is_err());
+        assert!(parse("{a: ''}").is_err());
+        assert!(parse("{a b}").is_err());
+    }
+
+    #[test]
+    fn test_unescape() {
+        assert_eq!(
+            unescape(r#""{a}\n\n{b: '\t'}""#).unwrap(),
+            "{a}\n\n{b: '\t'}"
+        );
+        assert_eq!(unescape(r#""\"\\\x41\u{e9}""#).unwrap(), "\"\\Aé");
+        assert_eq!(unescape("\"a\\\n    b\"").unwrap(), "ab");
+        assert_eq!(unescape(r###"r#"a"b\n"#"###).unwrap(), "a\"b\\n");
+        assert_eq!(unescape(r#"r"a""#).unwrap(), "a");
+
+        assert!(unescape("1").is_err());
+        assert!(unescape(r#""\q""#).is_err());
+    }
+}
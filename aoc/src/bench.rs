@@ -0,0 +1,186 @@
+use crate::{
+    Args, Parsed,
+    json::{self, Value},
+    print_header, workspace_dir,
+};
+use lib::{
+    Part,
+    bench::{self, Short, Stats},
+};
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+pub fn bench(args: &Args) -> Result<(), String> {
+    let days = args.days()?;
+    let parts = args.parts();
+
+    let history_path = args
+        .history
+        .clone()
+        .unwrap_or_else(|| workspace_dir().join("bench_history.json"));
+    let mut history = History::load(&history_path)?;
+
+    // Results of explicitly given inputs can not be compared to each other.
+    let input_key = args.source.answers_key();
+
+    let mut regressions = vec![];
+    for (i, day) in days.iter().enumerate() {
+        print_header(i, days.len(), day);
+
+        let mut parsed = None;
+        for &part in &parts {
+            let (p, fresh) = Parsed::for_part(parsed.take(), day, &args.source, part)?;
+
+            let mut phases = vec![];
+            if fresh {
+                let name = match part {
+                    Part::One => "parse".to_owned(),
+                    _ => format!("parse{part}"),
+                };
+                let stats = bench::bench(args.warmup, args.iterations, || day.parse(&p.raw));
+                phases.push((name, stats));
+            }
+
+            let stats = bench::bench(args.warmup, args.iterations, || {
+                day.solve(p.input.as_ref(), part)
+            });
+            phases.push((format!("part{part}"), stats));
+
+            for (phase, stats) in phases {
+                let key = input_key.map(|input| format!("day-{:02}/{input}/{phase}", day.day));
+                let regression = key.as_ref().and_then(|key| {
+                    let last = history.last(key)?;
+                    let change = stats.change(&last);
+                    (change > args.threshold).then_some((last, change))
+                });
+
+                print!("{phase:<7} {stats}");
+                if let Some((last, change)) = regression {
+                    print!(
+                        "  ⚠ regressed by {change:.1}% ({} → {})",
+                        Short(last.median),
+                        Short(stats.median)
+                    );
+                    regressions.push(format!("day {:02} {phase} (+{change:.1}%)", day.day));
+                }
+                println!();
+
+                if let Some(key) = key {
+                    history.push(&key, &stats);
+                }
+            }
+
+            parsed = Some(p);
+        }
+    }
+
+    if input_key.is_some() {
+        history.save()?;
+    }
+
+    if !regressions.is_empty() {
+        println!(
+            "\n{} phases regressed by more than {}%: {}",
+            regressions.len(),
+            args.threshold,
+            regressions.join(", ")
+        );
+    }
+
+    Ok(())
+}
+
+/// Benchmark results of earlier runs, stored as a JSON object mapping
+/// `day-XX/<input>/<phase>` to a list of results, the most recent last.
+struct History {
+    path: PathBuf,
+    series: Vec<(String, Vec<Value>)>,
+}
+
+impl History {
+    /// Loads the history from `path`. A missing file results in an empty
+    /// history.
+    fn load(path: &Path) -> Result<Self, String> {
+        let series = match std::fs::read_to_string(path) {
+            Ok(s) => {
+                let root: Value = s.parse().map_err(|e| format!("{}: {e}", path.display()))?;
+                root.as_object()
+                    .ok_or_else(|| format!("{}: expected an object", path.display()))?
+                    .iter()
+                    .map(|(k, v)| (k.clone(), v.as_array().unwrap_or_default().to_vec()))
+                    .collect()
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => vec![],
+            Err(e) => return Err(format!("failed reading {}: {e}", path.display())),
+        };
+
+        Ok(Self {
+            path: path.to_owned(),
+            series,
+        })
+    }
+
+    /// Returns the most recent result stored for `key`.
+    fn last(&self, key: &str) -> Option<Stats> {
+        let (_, entries) = self.series.iter().find(|(k, _)| k == key)?;
+        let entry = entries.last()?;
+        let ns = |field| {
+            entry
+                .get(field)?
+                .as_f64()
+                .map(|n| Duration::from_nanos(n as u64))
+        };
+
+        Some(Stats {
+            iterations: entry.get("iterations")?.as_f64()? as usize,
+            min: ns("min_ns")?,
+            max: ns("max_ns")?,
+            median: ns("median_ns")?,
+            mean: ns("mean_ns")?,
+            stddev: ns("stddev_ns")?,
+        })
+    }
+
+    /// Appends a result for `key`.
+    fn push(&mut self, key: &str, stats: &Stats) {
+        let timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let ns = |d: Duration| Value::from(d.as_nanos() as u64);
+
+        let entry = json::object([
+            ("timestamp", timestamp.into()),
+            ("iterations", stats.iterations.into()),
+            ("min_ns", ns(stats.min)),
+            ("max_ns", ns(stats.max)),
+            ("median_ns", ns(stats.median)),
+            ("mean_ns", ns(stats.mean)),
+            ("stddev_ns", ns(stats.stddev)),
+        ]);
+
+        match self.series.iter_mut().find(|(k, _)| k == key) {
+            Some((_, entries)) => entries.push(entry),
+            None => self.series.push((key.to_owned(), vec![entry])),
+        }
+    }
+
+    fn save(&self) -> Result<(), String> {
+        // One line per series keeps the file readable and diffable.
+        let mut out = String::from("{\n");
+        for (i, (key, entries)) in self.series.iter().enumerate() {
+            let sep = if i + 1 < self.series.len() { "," } else { "" };
+            out += &format!(
+                "  {}: {}{sep}\n",
+                Value::from(key.as_str()),
+                Value::Array(entries.clone())
+            );
+        }
+        out += "}\n";
+
+        std::fs::write(&self.path, out)
+            .map_err(|e| format!("failed writing {}: {e}", self.path.display()))
+    }
+}
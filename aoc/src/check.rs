@@ -0,0 +1,264 @@
+use crate::{Args, day_dir, print_header, run::run_day, workspace_dir};
+use core::fmt;
+use lib::{Check, Day, InputLocation, InputSource, Part, bench::Short};
+use std::{
+    env, fs,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    process::Command,
+    time::Duration,
+};
+
+/// Result of a single check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Pass,
+    /// The check could not be done, like for a missing input.
+    Skip,
+    Fail,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pass => write!(f, "✓"),
+            Self::Skip => write!(f, "-"),
+            Self::Fail => write!(f, "✗"),
+        }
+    }
+}
+
+/// Outcome of one check of a day.
+struct Finding {
+    status: Status,
+    message: String,
+}
+
+impl Finding {
+    fn new(status: Status, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.status, self.message)
+    }
+}
+
+/// Checks that the selected days are ready to be committed: no TODOs are
+/// left, the tests pass, the answers match the expected ones and the
+/// solutions stay within the runtime budget.
+pub fn check(args: &Args) -> Result<(), String> {
+    let days = args.days()?;
+
+    let mut failed = vec![];
+    for (i, day) in days.iter().enumerate() {
+        print_header(i, days.len(), day);
+
+        let findings = check_day(day, args.budget);
+        for finding in &findings {
+            println!("{finding}");
+        }
+        if findings.iter().any(|f| f.status == Status::Fail) {
+            failed.push(format!("{:02}", day.day));
+        }
+    }
+
+    if days.len() > 1 {
+        println!(
+            "\n{} of {} days passed",
+            days.len() - failed.len(),
+            days.len()
+        );
+    }
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("checks failed for day {}", failed.join(", ")))
+    }
+}
+
+fn check_day(day: &Day, budget: Duration) -> Vec<Finding> {
+    let dir = day_dir(day.day);
+    let mut findings = vec![];
+
+    let todos = find_todos(&dir.join("src"));
+    findings.push(match todos.as_slice() {
+        [] => Finding::new(Status::Pass, "no TODOs left"),
+        todos => {
+            let todos: Vec<_> = todos
+                .iter()
+                .map(|(path, line)| {
+                    let path = path.strip_prefix(workspace_dir()).unwrap_or(path);
+                    format!("{}:{line}", path.display())
+                })
+                .collect();
+            Finding::new(Status::Fail, format!("TODOs left in {}", todos.join(", ")))
+        }
+    });
+
+    findings.push(run_tests(day.day));
+
+    for source in [InputSource::Example, InputSource::Puzzle] {
+        let name = match source {
+            InputSource::Example => "example",
+            _ => "puzzle",
+        };
+        if let InputLocation::File(path) = source.locate(&dir, Part::One) {
+            if fs::read_to_string(&path).is_ok_and(|s| s.trim().is_empty()) {
+                findings.push(Finding::new(Status::Skip, format!("{name} input is empty")));
+                continue;
+            }
+            if !path.is_file() {
+                findings.push(Finding::new(Status::Skip, format!("no {name} input")));
+                continue;
+            }
+        }
+
+        let mut records = vec![];
+        // A panicking solution must not abort the checks of other days.
+        let res = panic::catch_unwind(AssertUnwindSafe(|| {
+            run_day(day, &Part::all(), &source, |r| records.push(r))
+        }));
+        let err = match res {
+            Ok(Ok(())) => None,
+            Ok(Err(err)) => Some(err),
+            Err(_) => Some("the solution panicked".into()),
+        };
+        if let Some(err) = err {
+            findings.push(Finding::new(
+                Status::Fail,
+                format!("running the {name} input failed: {err}"),
+            ));
+            continue;
+        }
+
+        for record in &records {
+            let part = record.part;
+            findings.push(match &record.check {
+                Check::Correct => Finding::new(
+                    Status::Pass,
+                    format!("{name} part {part} is {}", record.answer),
+                ),
+                Check::Wrong { expected } => Finding::new(
+                    Status::Fail,
+                    format!(
+                        "{name} part {part} is {}, expected {expected}",
+                        record.answer
+                    ),
+                ),
+                Check::Unverified => Finding::new(
+                    Status::Skip,
+                    format!("{name} part {part} is unverified, there is no expected answer"),
+                ),
+            });
+        }
+
+        // The budget applies to the actual puzzle input only.
+        if source == InputSource::Puzzle {
+            let total: Duration = records
+                .iter()
+                .map(|r| r.parse.unwrap_or_default() + r.duration)
+                .sum();
+            let status = if total <= budget {
+                Status::Pass
+            } else {
+                Status::Fail
+            };
+            findings.push(Finding::new(
+                status,
+                format!("runtime {} (budget {})", Short(total), Short(budget)),
+            ));
+        }
+    }
+
+    findings
+}
+
+/// Runs the unit tests of a day with cargo.
+fn run_tests(day: u8) -> Finding {
+    // When run through cargo, use the same cargo and toolchain.
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".into());
+    let res = Command::new(cargo)
+        .args(["test", "--quiet", "--package", &format!("day-{day:02}")])
+        .output();
+
+    match res {
+        Ok(out) if out.status.success() => Finding::new(Status::Pass, "tests pass"),
+        Ok(out) => {
+            let stdout = String::from_utf8_lossy(&out.stdout);
+            let stderr = String::from_utf8_lossy(&out.stderr);
+            // The failing tests are listed at the end of the output.
+            let tail: Vec<_> = stdout
+                .lines()
+                .chain(stderr.lines())
+                .filter(|l| !l.trim().is_empty())
+                .collect();
+            let tail = tail[tail.len().saturating_sub(10)..].join("\n    ");
+            Finding::new(Status::Fail, format!("tests fail:\n    {tail}"))
+        }
+        Err(err) => Finding::new(Status::Fail, format!("failed running cargo: {err}")),
+    }
+}
+
+/// Returns the files and line numbers of `TODO` comments and `todo!()`
+/// calls in the Rust files in `dir`.
+fn find_todos(dir: &Path) -> Vec<(PathBuf, usize)> {
+    let mut res = vec![];
+    let Ok(entries) = fs::read_dir(dir) else {
+        return res;
+    };
+
+    let mut paths: Vec<_> = entries.filter_map(|e| Some(e.ok()?.path())).collect();
+    paths.sort();
+    for path in paths {
+        if path.is_dir() {
+            res.extend(find_todos(&path));
+            continue;
+        }
+        if path.extension().is_none_or(|e| e != "rs") {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        for (i, line) in content.lines().enumerate() {
+            if line.contains("TODO") || line.contains("todo!(") {
+                res.push((path.clone(), i + 1));
+            }
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_find_todos() {
+        let dir = env::temp_dir().join(format!("aoc-check-test-{}", std::process::id()));
+        fs::create_dir_all(dir.join("src/grid")).unwrap();
+        fs::write(dir.join("src/lib.rs"), "fn a() {\n    todo!()\n}\n").unwrap();
+        fs::write(dir.join("src/grid/mod.rs"), "// TODO: faster\n").unwrap();
+        fs::write(dir.join("src/done.rs"), "fn b() {}\n").unwrap();
+        fs::write(dir.join("notes.txt"), "TODO\n").unwrap();
+
+        let todos = find_todos(&dir);
+        assert_eq!(
+            todos,
+            [
+                (dir.join("src/grid/mod.rs"), 1),
+                (dir.join("src/lib.rs"), 2)
+            ]
+        );
+        assert!(find_todos(&dir.join("missing")).is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
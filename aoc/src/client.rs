@@ -0,0 +1,561 @@
+use crate::{
+    html,
+    http::{Http, Method, Request, Response},
+};
+use core::fmt;
+use lib::Part;
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+const BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/zekroTJA/aoc2024 by zekroTJA (aoc runner)";
+const DEFAULT_YEAR: u16 = 2024;
+
+/// Error returned by the [`Client`].
+#[derive(Debug)]
+pub enum ClientError {
+    /// No session token has been configured.
+    MissingToken,
+    /// The website did not accept the session token.
+    NotLoggedIn,
+    /// The puzzle of the day has not been unlocked yet.
+    NotFound { day: u8 },
+    /// The website responded with an unexpected status code.
+    Status(u16),
+    /// The response to a submitted answer could not be understood.
+    UnknownReply(String),
+    /// The request could not be sent.
+    Transport(String),
+    /// Reading or writing the cache failed.
+    Cache {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingToken => write!(
+                f,
+                "no session token found, set SESSION_TOKEN in the environment or in .env"
+            ),
+            Self::NotLoggedIn => write!(
+                f,
+                "the session token has been rejected, it has probably expired"
+            ),
+            Self::NotFound { day } => write!(f, "the puzzle of day {day} is not available yet"),
+            Self::Status(status) => write!(f, "unexpected response status {status}"),
+            Self::UnknownReply(msg) => write!(f, "unexpected reply to the answer: {msg}"),
+            Self::Transport(err) => write!(f, "{err}"),
+            Self::Cache { path, source } => {
+                write!(f, "failed accessing cache {}: {source}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+/// Reads the session token from the `SESSION_TOKEN` environment variable or
+/// from the `.env` file in `dir`.
+pub fn session_token(dir: &Path) -> Option<String> {
+    if let Some(token) = env::var("SESSION_TOKEN").ok().filter(|t| !t.is_empty()) {
+        return Some(token);
+    }
+
+    let env_file = fs::read_to_string(dir.join(".env")).ok()?;
+    env_file
+        .lines()
+        .filter_map(|l| l.trim().split_once('='))
+        .find(|(k, _)| k.trim().trim_start_matches("export ") == "SESSION_TOKEN")
+        .map(|(_, v)| v.trim().trim_matches(['"', '\'']).to_owned())
+        .filter(|t| !t.is_empty())
+}
+
+/// Client for the Advent of Code website. Fetched inputs are cached and
+/// requests are spaced out by at least the throttle interval, also across
+/// separate runs.
+pub struct Client<H> {
+    http: H,
+    base_url: String,
+    year: u16,
+    session: Option<String>,
+    cache_dir: PathBuf,
+    throttle: Duration,
+}
+
+impl<H: Http> Client<H> {
+    /// Creates a client caching into `cache_dir`. The year is read from the
+    /// `YEAR` environment variable and defaults to 2024.
+    pub fn new(http: H, session: Option<String>, cache_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            http,
+            base_url: BASE_URL.into(),
+            year: env::var("YEAR")
+                .ok()
+                .and_then(|y| y.parse().ok())
+                .unwrap_or(DEFAULT_YEAR),
+            session,
+            cache_dir: cache_dir.into(),
+            throttle: Duration::from_secs(5),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_throttle(mut self, throttle: Duration) -> Self {
+        self.throttle = throttle;
+        self
+    }
+
+    /// Returns the puzzle input of `day`, fetching it only if it is not
+    /// cached yet.
+    pub fn input(&self, day: u8) -> Result<String, ClientError> {
+        let path = self
+            .cache_dir
+            .join(format!("{}/day-{day:02}/input.txt", self.year));
+        if let Ok(input) = fs::read_to_string(&path) {
+            return Ok(input);
+        }
+
+        let req = Request::new(Method::Get, self.url(&format!("day/{day}/input")));
+        let res = self.send(req)?;
+        let input = match res.status {
+            // Inputs are never HTML, so this is the page asking to log in.
+            200 if !looks_like_html(&res.body) => res.body,
+            404 => return Err(ClientError::NotFound { day }),
+            _ if looks_like_html(&res.body) || is_login_error(&res.body) => {
+                return Err(ClientError::NotLoggedIn);
+            }
+            status => return Err(ClientError::Status(status)),
+        };
+
+        write_file(&path, &input)?;
+        Ok(input)
+    }
+
+    /// Submits `answer` for `part` of `day` and returns the reply of the
+    /// website.
+    pub fn submit(&self, day: u8, part: Part, answer: &str) -> Result<Reply, ClientError> {
+        let req = Request::new(Method::Post, self.url(&format!("day/{day}/answer")))
+            .form(&[("level", &part.to_string()), ("answer", answer)]);
+        let res = self.send(req)?;
+        match res.status {
+            200 => Reply::parse(&res.body).ok_or_else(|| {
+                if is_login_error(&res.body) {
+                    ClientError::NotLoggedIn
+                } else {
+                    ClientError::UnknownReply(article(&res.body).unwrap_or_default())
+                }
+            }),
+            404 => Err(ClientError::NotFound { day }),
+            _ if is_login_error(&res.body) => Err(ClientError::NotLoggedIn),
+            status => Err(ClientError::Status(status)),
+        }
+    }
+
+    /// Returns the year the client is fetching puzzles of.
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/{}/{path}", self.base_url, self.year)
+    }
+
+    /// Sends `req` with the session cookie, respecting the throttle.
+    fn send(&self, req: Request) -> Result<Response, ClientError> {
+        let session = self.session.as_ref().ok_or(ClientError::MissingToken)?;
+        let req = req
+            .header("Cookie", &format!("session={session}"))
+            .header("User-Agent", USER_AGENT);
+
+        self.wait_for_throttle()?;
+        self.http.send(&req).map_err(ClientError::Transport)
+    }
+
+    /// Sleeps until the throttle interval since the last request has passed
+    /// and records the current request. The time of the last request is
+    /// stored in the cache, so that it is respected across runs.
+    fn wait_for_throttle(&self) -> Result<(), ClientError> {
+        let path = self.cache_dir.join("last_request");
+        let wait = fs::metadata(&path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|last| last.elapsed().ok())
+            .and_then(|elapsed| self.throttle.checked_sub(elapsed));
+        if let Some(wait) = wait {
+            thread::sleep(wait);
+        }
+
+        write_file(&path, "")?;
+        // Writing an empty file might not update the modification time on
+        // every platform.
+        fs::File::options()
+            .write(true)
+            .open(&path)
+            .and_then(|f| f.set_modified(SystemTime::now()))
+            .map_err(|source| ClientError::Cache { path, source })
+    }
+}
+
+fn write_file(path: &Path, content: &str) -> Result<(), ClientError> {
+    let err = |source| ClientError::Cache {
+        path: path.to_owned(),
+        source,
+    };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(err)?;
+    }
+    fs::write(path, content).map_err(err)
+}
+
+/// Returns true if the body is an HTML page, which a puzzle input never is.
+fn looks_like_html(body: &str) -> bool {
+    let start = body.trim_start().to_ascii_lowercase();
+    start.starts_with("<!doctype") || start.starts_with("<html")
+}
+
+/// Returns true if the body asks to log in, which happens when the session
+/// token is missing or expired.
+fn is_login_error(body: &str) -> bool {
+    body.contains("log in") || body.contains("[Log In]")
+}
+
+/// Verdict of the website on a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint in which direction.
+    Wrong,
+    /// The answer has not been checked, because the last one was submitted
+    /// too recently.
+    RateLimited,
+    /// The part has already been solved or is not unlocked yet.
+    WrongLevel,
+}
+
+impl Outcome {
+    /// Returns true if the answer has been checked and rejected.
+    pub fn is_rejected(self) -> bool {
+        matches!(self, Self::TooHigh | Self::TooLow | Self::Wrong)
+    }
+
+    /// Returns the identifier used in the attempt log.
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Correct => "correct",
+            Self::TooHigh => "too_high",
+            Self::TooLow => "too_low",
+            Self::Wrong => "wrong",
+            Self::RateLimited => "rate_limited",
+            Self::WrongLevel => "wrong_level",
+        }
+    }
+
+    /// Parses an identifier returned by [`Outcome::key`].
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "correct" => Some(Self::Correct),
+            "too_high" => Some(Self::TooHigh),
+            "too_low" => Some(Self::TooLow),
+            "wrong" => Some(Self::Wrong),
+            "rate_limited" => Some(Self::RateLimited),
+            "wrong_level" => Some(Self::WrongLevel),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Correct => write!(f, "correct"),
+            Self::TooHigh => write!(f, "too high"),
+            Self::TooLow => write!(f, "too low"),
+            Self::Wrong => write!(f, "wrong"),
+            Self::RateLimited => write!(f, "submitted too recently"),
+            Self::WrongLevel => write!(f, "already solved or not unlocked yet"),
+        }
+    }
+}
+
+/// Reply of the website to a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reply {
+    pub outcome: Outcome,
+    /// How long to wait before the next answer can be submitted.
+    pub wait: Option<Duration>,
+    /// The text of the reply.
+    pub message: String,
+}
+
+impl Reply {
+    /// Parses the reply from the HTML page returned for a submitted answer.
+    /// Returns `None` if the page does not contain a known reply.
+    pub fn parse(html: &str) -> Option<Self> {
+        let message = article(html)?;
+        let outcome = if message.starts_with("That's the right answer") {
+            Outcome::Correct
+        } else if message.starts_with("That's not the right answer") {
+            if message.contains("answer is too high") {
+                Outcome::TooHigh
+            } else if message.contains("answer is too low") {
+                Outcome::TooLow
+            } else {
+                Outcome::Wrong
+            }
+        } else if message.starts_with("You gave an answer too recently") {
+            Outcome::RateLimited
+        } else if message.starts_with("You don't seem to be solving the right level") {
+            Outcome::WrongLevel
+        } else {
+            return None;
+        };
+
+        Some(Self {
+            outcome,
+            wait: parse_wait(&message),
+            message,
+        })
+    }
+}
+
+/// Returns the text of the first `<article>` of `html` with collapsed
+/// whitespace.
+fn article(html: &str) -> Option<String> {
+    let article = html::text(html::elements(html, "article").first()?);
+    Some(article.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+/// Parses the time to wait from a reply, which is given either like
+/// `You have 1m 38s left to wait` or like `please wait 5 minutes before
+/// trying again`.
+fn parse_wait(message: &str) -> Option<Duration> {
+    let lower = message.to_lowercase();
+
+    if let Some((before, _)) = lower.split_once(" left to wait") {
+        let (_, time) = before.rsplit_once("you have ")?;
+        let mut secs = 0;
+        for part in time.split_whitespace() {
+            let unit = part.trim_start_matches(|c: char| c.is_ascii_digit());
+            let n: u64 = part[..part.len() - unit.len()].parse().ok()?;
+            secs += n * match unit {
+                "h" => 3600,
+                "m" => 60,
+                "s" => 1,
+                _ => return None,
+            };
+        }
+        return Some(Duration::from_secs(secs));
+    }
+
+    let (_, rest) = lower.split_once("wait ")?;
+    let mut words = rest.split_whitespace();
+    let n = match words.next()? {
+        "one" | "a" | "an" => 1,
+        n => n.parse().ok()?,
+    };
+    let secs = match words.next()?.trim_end_matches(['.', ',']) {
+        "second" | "seconds" => 1,
+        "minute" | "minutes" => 60,
+        "hour" | "hours" => 3600,
+        _ => return None,
+    };
+    Some(Duration::from_secs(n * secs))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::http::FakeServer;
+    use std::time::Instant;
+
+    fn cache_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-client-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn client(server: FakeServer, cache: &Path) -> Client<FakeServer> {
+        let mut client = Client::new(server, Some("token".into()), cache)
+            .with_base_url("http://aoc")
+            .with_throttle(Duration::ZERO);
+        client.year = 2024;
+        client
+    }
+
+    #[test]
+    fn test_input_is_cached() {
+        let cache = cache_dir("cached");
+        let server =
+            FakeServer::default().route(Method::Get, "http://aoc/2024/day/3/input", 200, "1 2\n");
+        let client = client(server, &cache);
+
+        assert_eq!(client.input(3).unwrap(), "1 2\n");
+        assert_eq!(client.input(3).unwrap(), "1 2\n");
+
+        let requests = client.http.requests.borrow();
+        assert_eq!(requests.len(), 1);
+        assert!(
+            requests[0]
+                .headers
+                .contains(&("Cookie".into(), "session=token".into()))
+        );
+        assert!(cache.join("2024/day-03/input.txt").is_file());
+
+        fs::remove_dir_all(&cache).unwrap();
+    }
+
+    #[test]
+    fn test_errors_are_not_cached() {
+        let cache = cache_dir("errors");
+        let login = "Puzzle inputs differ by user.  Please log in to get your puzzle input.";
+        let server = FakeServer::default()
+            .route(Method::Get, "http://aoc/2024/day/1/input", 400, login)
+            .route(
+                Method::Get,
+                "http://aoc/2024/day/2/input",
+                200,
+                "<!DOCTYPE html>\n<html><a href=\"/2024/auth/login\">[Log In]</a></html>",
+            )
+            .route(Method::Get, "http://aoc/2024/day/3/input", 500, "oops");
+        let client = client(server, &cache);
+
+        assert!(matches!(client.input(1), Err(ClientError::NotLoggedIn)));
+        assert!(matches!(client.input(2), Err(ClientError::NotLoggedIn)));
+        assert!(matches!(client.input(3), Err(ClientError::Status(500))));
+        assert!(matches!(
+            client.input(25),
+            Err(ClientError::NotFound { day: 25 })
+        ));
+        assert!(!cache.join("2024").exists());
+
+        let client = Client::new(FakeServer::default(), None, &cache);
+        assert!(matches!(client.input(1), Err(ClientError::MissingToken)));
+
+        fs::remove_dir_all(&cache).unwrap();
+    }
+
+    #[test]
+    fn test_throttle() {
+        let cache = cache_dir("throttle");
+        let client =
+            client(FakeServer::default(), &cache).with_throttle(Duration::from_millis(100));
+
+        let start = Instant::now();
+        for day in 1..=3 {
+            let _ = client.input(day);
+        }
+        assert!(start.elapsed() >= Duration::from_millis(200));
+
+        fs::remove_dir_all(&cache).unwrap();
+    }
+
+    #[test]
+    fn test_session_token_from_env_file() {
+        let dir = cache_dir("env");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(".env"), "OTHER=1\nSESSION_TOKEN=\"abc123\"\n").unwrap();
+
+        // The environment variable takes precedence, which can not be
+        // changed safely from a test.
+        if env::var("SESSION_TOKEN").is_err() {
+            assert_eq!(session_token(&dir).as_deref(), Some("abc123"));
+        }
+
+        fs::write(dir.join(".env"), "SESSION_TOKEN=\"\"\n").unwrap();
+        if env::var("SESSION_TOKEN").is_err() {
+            assert_eq!(session_token(&dir), None);
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_parse_reply() {
+        let parse = |html| Reply::parse(html).unwrap();
+        let minute = Some(Duration::from_secs(60));
+
+        let reply = parse(include_str!("../fixtures/submit/correct.html"));
+        assert_eq!(reply.outcome, Outcome::Correct);
+        assert_eq!(reply.wait, None);
+        assert_eq!(
+            reply.message,
+            "That's the right answer! You are one gold star closer to finding the Chief \
+             Historian. [Continue to Part Two]"
+        );
+
+        let reply = parse(include_str!("../fixtures/submit/too_high.html"));
+        assert_eq!((reply.outcome, reply.wait), (Outcome::TooHigh, minute));
+        let reply = parse(include_str!("../fixtures/submit/too_low.html"));
+        assert_eq!((reply.outcome, reply.wait), (Outcome::TooLow, minute));
+        let reply = parse(include_str!("../fixtures/submit/wrong.html"));
+        assert_eq!(
+            (reply.outcome, reply.wait),
+            (Outcome::Wrong, Some(Duration::from_secs(300)))
+        );
+        let reply = parse(include_str!("../fixtures/submit/rate_limited.html"));
+        assert_eq!(
+            (reply.outcome, reply.wait),
+            (Outcome::RateLimited, Some(Duration::from_secs(98)))
+        );
+        let reply = parse(include_str!("../fixtures/submit/already_solved.html"));
+        assert_eq!((reply.outcome, reply.wait), (Outcome::WrongLevel, None));
+
+        assert_eq!(Reply::parse("<html></html>"), None);
+        assert_eq!(
+            Reply::parse("<article><p>Something else</p></article>"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_submit() {
+        let cache = cache_dir("submit");
+        let server = FakeServer::default()
+            .route(
+                Method::Post,
+                "http://aoc/2024/day/1/answer",
+                200,
+                include_str!("../fixtures/submit/too_low.html"),
+            )
+            .route(
+                Method::Post,
+                "http://aoc/2024/day/2/answer",
+                200,
+                "<!DOCTYPE html><html><a href=\"/2024/auth/login\">[Log In]</a></html>",
+            );
+        let client = client(server, &cache);
+
+        let reply = client.submit(1, Part::Two, "1234").unwrap();
+        assert_eq!(reply.outcome, Outcome::TooLow);
+        assert_eq!(
+            client.http.requests.borrow()[0].body.as_deref(),
+            Some("level=2&answer=1234")
+        );
+
+        assert!(matches!(
+            client.submit(2, Part::One, "1"),
+            Err(ClientError::NotLoggedIn)
+        ));
+        assert!(matches!(
+            client.submit(3, Part::One, "1"),
+            Err(ClientError::NotFound { day: 3 })
+        ));
+
+        fs::remove_dir_all(&cache).unwrap();
+    }
+}
@@ -0,0 +1,351 @@
+use crate::{day_dir, html};
+use lib::{Answers, Part};
+use std::{fs, path::PathBuf};
+
+/// Arguments of the `examples` command.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ExamplesArgs {
+    day: u8,
+    puzzle: Option<PathBuf>,
+    example: Option<usize>,
+    example2: Option<usize>,
+    force: bool,
+}
+
+impl ExamplesArgs {
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut day = None;
+        let mut res = Self {
+            day: 0,
+            puzzle: None,
+            example: None,
+            example2: None,
+            force: false,
+        };
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or_else(|| format!("{arg} requires a value"));
+            let index = |v: String| {
+                v.parse()
+                    .ok()
+                    .filter(|&n| n > 0)
+                    .ok_or_else(|| format!("invalid value '{v}' for {arg}"))
+            };
+
+            match arg.as_str() {
+                "--puzzle" => res.puzzle = Some(value()?.into()),
+                "--example" => res.example = Some(index(value()?)?),
+                "--example2" => res.example2 = Some(index(value()?)?),
+                "--force" => res.force = true,
+                _ if arg.starts_with('-') => return Err(format!("unknown flag '{arg}'")),
+                _ => day = Some(arg.parse().map_err(|_| format!("invalid day '{arg}'"))?),
+            }
+        }
+
+        res.day = day.ok_or("expected a day")?;
+        Ok(res)
+    }
+}
+
+/// Extracts the example inputs and answers from the saved puzzle page of a
+/// day and writes them to its `test_input.txt` and `answers.toml`.
+pub fn examples(args: &ExamplesArgs) -> Result<(), String> {
+    let dir = day_dir(args.day);
+    if !dir.is_dir() {
+        return Err(format!("{} does not exist", dir.display()));
+    }
+
+    let path = args
+        .puzzle
+        .clone()
+        .unwrap_or_else(|| dir.join("puzzle.html"));
+    let html =
+        fs::read_to_string(&path).map_err(|e| format!("failed reading {}: {e}", path.display()))?;
+    let puzzle = Puzzle::parse(&html)?;
+
+    for (i, (part, example)) in puzzle.examples().enumerate() {
+        let lines: Vec<_> = example.lines().collect();
+        println!("Example {} (part {part}, {} lines):", i + 1, lines.len());
+        for line in lines.iter().take(3) {
+            println!("    {line}");
+        }
+        if lines.len() > 3 {
+            println!("    ...");
+        }
+    }
+
+    let example = |n: usize| {
+        puzzle
+            .examples()
+            .nth(n - 1)
+            .map(|(_, e)| e)
+            .ok_or_else(|| format!("there is no example {n}"))
+    };
+    let default = puzzle
+        .parts
+        .first()
+        .and_then(|p| p.examples.first())
+        .ok_or("no examples found")?;
+
+    let inputs = [
+        (
+            "test_input.txt",
+            args.example.map(example).unwrap_or(Ok(default))?,
+        ),
+        (
+            "test_input_2.txt",
+            match args.example2 {
+                Some(n) => example(n)?,
+                None => "",
+            },
+        ),
+    ];
+    for (file, input) in inputs.into_iter().filter(|(_, i)| !i.is_empty()) {
+        let path = dir.join(file);
+        let existing = fs::read_to_string(&path).unwrap_or_default();
+        if !existing.trim().is_empty() && !args.force {
+            println!("Kept existing {file}, use --force to replace it");
+            continue;
+        }
+        fs::write(&path, input).map_err(|e| format!("failed writing {}: {e}", path.display()))?;
+        println!("Wrote {file}");
+    }
+
+    let mut answers = vec![];
+    for (part, desc) in Part::all().into_iter().zip(&puzzle.parts) {
+        let Some(answer) = desc.answers.last() else {
+            continue;
+        };
+        println!(
+            "Part {part} example answer: {answer} (emphasised: {})",
+            desc.answers.join(", ")
+        );
+        answers.push((part, answer.as_str()));
+    }
+    if !answers.is_empty() {
+        let path = dir.join("answers.toml");
+        let toml = fs::read_to_string(&path).unwrap_or_default();
+        let updated = add_example_answers(&toml, &answers, args.force)?;
+        if updated != toml {
+            fs::write(&path, updated)
+                .map_err(|e| format!("failed writing {}: {e}", path.display()))?;
+            println!("Updated answers.toml");
+        } else {
+            println!("Kept existing answers, use --force to replace them");
+        }
+    }
+
+    Ok(())
+}
+
+/// Examples found in the description of a part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Description {
+    /// Contents of the `<pre><code>` blocks.
+    examples: Vec<String>,
+    /// Emphasised code outside of the examples, the last one usually being
+    /// the answer of the example.
+    answers: Vec<String>,
+}
+
+/// Examples of a puzzle page, one description per part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Puzzle {
+    parts: Vec<Description>,
+}
+
+impl Puzzle {
+    fn parse(html: &str) -> Result<Self, String> {
+        let parts: Vec<_> = html::elements(html, "article")
+            .into_iter()
+            .map(|article| {
+                let blocks = html::elements(article, "pre");
+                let examples = blocks.iter().map(|pre| html::text(pre)).collect();
+
+                let mut rest = article.to_owned();
+                for pre in &blocks {
+                    rest = rest.replace(pre, "");
+                }
+                let rest = rest
+                    .replace("<em><code>", "<code><em>")
+                    .replace("</code></em>", "</em></code>");
+                let answers = html::elements(&rest, "code")
+                    .into_iter()
+                    .filter_map(|c| c.strip_prefix("<em>")?.strip_suffix("</em>"))
+                    .map(html::text)
+                    .collect();
+
+                Description { examples, answers }
+            })
+            .collect();
+
+        if parts.is_empty() {
+            return Err("no puzzle description found".into());
+        }
+        Ok(Self { parts })
+    }
+
+    /// Returns all examples with the part they are given in.
+    fn examples(&self) -> impl Iterator<Item = (Part, &str)> {
+        Part::all()
+            .into_iter()
+            .zip(&self.parts)
+            .flat_map(|(part, desc)| desc.examples.iter().map(move |e| (part, e.as_str())))
+    }
+}
+
+/// Returns the range of lines after the header of `[name]` up to the next
+/// table header.
+fn find_table(lines: &[String], name: &str) -> Option<(usize, usize)> {
+    let header = format!("[{name}]");
+    let start = lines.iter().position(|l| l.trim() == header)? + 1;
+    let end = (start..lines.len())
+        .find(|&i| lines[i].trim_start().starts_with('['))
+        .unwrap_or(lines.len());
+    Some((start, end))
+}
+
+/// Sets the `example` answers in the contents of an `answers.toml` file.
+/// Existing answers are only replaced if `force` is set.
+fn add_example_answers(
+    toml: &str,
+    answers: &[(Part, &str)],
+    force: bool,
+) -> Result<String, String> {
+    let existing: Answers = toml.parse().map_err(|e| format!("answers.toml: {e}"))?;
+    let mut lines: Vec<String> = toml.lines().map(Into::into).collect();
+
+    let (start, end) = match find_table(&lines, "example") {
+        Some(range) => range,
+        None => {
+            if lines.is_empty() {
+                lines.push("# Expected answers, checked by `aoc run --verify`.".into());
+            }
+            if lines.last().is_some_and(|l| !l.trim().is_empty()) {
+                lines.push(String::new());
+            }
+            lines.push("[example]".into());
+            (lines.len(), lines.len())
+        }
+    };
+    // New answers are added after the last line of the table.
+    let mut insert_at = (start..end)
+        .rev()
+        .find(|&i| !lines[i].trim().is_empty())
+        .map_or(start, |i| i + 1);
+
+    for &(part, answer) in answers {
+        let key = format!("part{part}");
+        let line = format!("{key} = {}", toml_value(answer)?);
+        let current = (start..end).find(|&i| {
+            lines[i]
+                .split_once('=')
+                .is_some_and(|(k, _)| k.trim() == key)
+        });
+
+        match current {
+            Some(i) if force => lines[i] = line,
+            Some(_) => {}
+            // Answers given with dotted keys outside of the table are kept.
+            None if existing.get("example", part).is_some() => {}
+            None => {
+                lines.insert(insert_at, line);
+                insert_at += 1;
+            }
+        }
+    }
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Formats an answer as an integer if possible, as a string otherwise.
+fn toml_value(answer: &str) -> Result<String, String> {
+    let digits = answer.strip_prefix('-').unwrap_or(answer);
+    if !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()) {
+        Ok(answer.to_owned())
+    } else if answer.contains(['"', '\\', '\n']) {
+        Err(format!("the answer '{answer}' can not be stored"))
+    } else {
+        Ok(format!("\"{answer}\""))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const PUZZLE: &str = include_str!("../fixtures/puzzle/puzzle.html");
+
+    #[test]
+    fn test_parse_puzzle() {
+        let puzzle = Puzzle::parse(PUZZLE).unwrap();
+        assert_eq!(puzzle.parts.len(), 2);
+
+        let part1 = &puzzle.parts[0];
+        assert_eq!(
+            part1.examples,
+            ["3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n"]
+        );
+        assert_eq!(part1.answers, ["11"]);
+
+        let part2 = &puzzle.parts[1];
+        assert_eq!(part2.examples, ["3 * 3 = 9\n4 * 1 = 4\n2 * 0 = 0\n"]);
+        assert_eq!(part2.answers, ["3", "31"]);
+
+        let examples: Vec<_> = puzzle.examples().map(|(p, _)| p).collect();
+        assert_eq!(examples, [Part::One, Part::Two]);
+
+        assert!(Puzzle::parse("<html><body></body></html>").is_err());
+    }
+
+    #[test]
+    fn test_add_example_answers() {
+        let answers = [(Part::One, "11"), (Part::Two, "ab-c")];
+
+        assert_eq!(
+            add_example_answers("", &answers, false).unwrap(),
+            "# Expected answers, checked by `aoc run --verify`.\n\n\
+             [example]\npart1 = 11\npart2 = \"ab-c\"\n"
+        );
+
+        let toml = "# Expected answers\n\n[input]\npart1 = 5\n";
+        assert_eq!(
+            add_example_answers(toml, &answers, false).unwrap(),
+            "# Expected answers\n\n[input]\npart1 = 5\n\n[example]\npart1 = 11\npart2 = \"ab-c\"\n"
+        );
+
+        let toml = "[example]\npart1 = 1\n\n[input]\npart1 = 5\n";
+        assert_eq!(
+            add_example_answers(toml, &answers, false).unwrap(),
+            "[example]\npart1 = 1\npart2 = \"ab-c\"\n\n[input]\npart1 = 5\n"
+        );
+        assert_eq!(
+            add_example_answers(toml, &answers, true).unwrap(),
+            "[example]\npart1 = 11\npart2 = \"ab-c\"\n\n[input]\npart1 = 5\n"
+        );
+
+        let toml = "example.part2 = 3\n";
+        assert_eq!(
+            add_example_answers(toml, &answers, false).unwrap(),
+            "example.part2 = 3\n\n[example]\npart1 = 11\n"
+        );
+
+        assert!(add_example_answers("[example", &answers, false).is_err());
+        assert!(add_example_answers("", &[(Part::One, "\"")], false).is_err());
+    }
+
+    #[test]
+    fn test_parse_examples_args() {
+        let parse = |args: &[&str]| ExamplesArgs::parse(args.iter().map(|a| a.to_string()));
+
+        let args = parse(&["4", "--example", "2", "--force"]).unwrap();
+        assert_eq!(args.day, 4);
+        assert_eq!(args.example, Some(2));
+        assert!(args.force);
+
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["4", "--example", "0"]).is_err());
+        assert!(parse(&["4", "--unknown"]).is_err());
+    }
+}
@@ -0,0 +1,38 @@
+use crate::{
+    Args,
+    client::{Client, session_token},
+    day_dir,
+    http::Curl,
+    workspace_dir,
+};
+use std::fs;
+
+pub fn fetch(args: &Args) -> Result<(), String> {
+    let client = client();
+    for day in args.days()? {
+        fetch_input(&client, day.day)?;
+    }
+    Ok(())
+}
+
+/// Returns the client for the Advent of Code website, caching into `.cache`
+/// in the workspace.
+pub fn client() -> Client<Curl> {
+    let root = workspace_dir();
+    Client::new(Curl, session_token(root), root.join(".cache/inputs"))
+}
+
+/// Writes the puzzle input of `day` to its `input.txt`, unless it already
+/// exists.
+pub fn fetch_input(client: &Client<Curl>, day: u8) -> Result<(), String> {
+    let path = day_dir(day).join("input.txt");
+    if path.is_file() {
+        println!("{} already exists", path.display());
+        return Ok(());
+    }
+
+    let input = client.input(day).map_err(|e| e.to_string())?;
+    fs::write(&path, input).map_err(|e| format!("failed writing {}: {e}", path.display()))?;
+    println!("Fetched input of day {day} into {}", path.display());
+    Ok(())
+}
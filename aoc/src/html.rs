@@ -0,0 +1,80 @@
+//! Minimal helpers to pick apart the HTML pages of the Advent of Code
+//! website. They only handle the well-formed markup used there and are no
+//! general HTML parser.
+
+/// Returns the inner HTML of all `<tag>` elements in `html`, in the order
+/// of their appearance. Nested elements of the same tag are not supported.
+pub fn elements<'a>(html: &'a str, tag: &str) -> Vec<&'a str> {
+    let open = format!("<{tag}");
+    let close = format!("</{tag}>");
+
+    let mut res = vec![];
+    let mut rest = html;
+    while let Some(start) = rest.find(&open) {
+        let after = &rest[start + open.len()..];
+        // Skip tags which merely start with the same name.
+        if !after.starts_with(['>', ' ', '\n', '\t']) {
+            rest = after;
+            continue;
+        }
+        let Some(content_start) = after.find('>') else {
+            break;
+        };
+        let content = &after[content_start + 1..];
+        let Some(end) = content.find(&close) else {
+            break;
+        };
+        res.push(&content[..end]);
+        rest = &content[end + close.len()..];
+    }
+    res
+}
+
+/// Returns the text of an HTML fragment with all tags removed and entities
+/// decoded.
+pub fn text(html: &str) -> String {
+    let mut res = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => res.push(c),
+            _ => {}
+        }
+    }
+    unescape(&res)
+}
+
+/// Decodes the character entities used on the website.
+fn unescape(s: &str) -> String {
+    s.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_elements() {
+        let html =
+            "<p>a <code>1</code></p><pre><code>x\n&lt;y&gt;</code></pre><p class=\"b\">c</p>";
+        assert_eq!(elements(html, "code"), ["1", "x\n&lt;y&gt;"]);
+        assert_eq!(elements(html, "p"), ["a <code>1</code>", "c"]);
+        assert_eq!(elements(html, "pre"), ["<code>x\n&lt;y&gt;</code>"]);
+        assert!(elements(html, "article").is_empty());
+    }
+
+    #[test]
+    fn test_text() {
+        assert_eq!(
+            text("<code>x\n&lt;<em>y</em>&gt; &amp;lt;</code>"),
+            "x\n<y> &lt;"
+        );
+    }
+}
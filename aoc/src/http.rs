@@ -0,0 +1,255 @@
+use core::fmt;
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Method {
+    Get,
+    Post,
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Method::Get => write!(f, "GET"),
+            Method::Post => write!(f, "POST"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub method: Method,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+}
+
+impl Request {
+    pub fn new(method: Method, url: impl Into<String>) -> Self {
+        Self {
+            method,
+            url: url.into(),
+            headers: vec![],
+            body: None,
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Sets the body to the URL encoded `fields`, like submitted by an HTML
+    /// form.
+    pub fn form(self, fields: &[(&str, &str)]) -> Self {
+        let body = fields
+            .iter()
+            .map(|(k, v)| format!("{}={}", url_encode(k), url_encode(v)))
+            .collect::<Vec<_>>()
+            .join("&");
+        let mut req = self.header("Content-Type", "application/x-www-form-urlencoded");
+        req.body = Some(body);
+        req
+    }
+}
+
+/// Percent-encodes `s` for use in a URL query or form body.
+fn url_encode(s: &str) -> String {
+    let mut res = String::with_capacity(s.len());
+    for b in s.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                res.push(b as char)
+            }
+            b' ' => res.push('+'),
+            _ => res += &format!("%{b:02X}"),
+        }
+    }
+    res
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+/// Transport used to talk to the Advent of Code website. Implemented by
+/// [`Curl`] and by a stand-in server in tests.
+pub trait Http {
+    fn send(&self, req: &Request) -> Result<Response, String>;
+}
+
+/// Sends requests using the `curl` command line tool.
+///
+/// Headers and body are passed as a config on stdin rather than as
+/// arguments, so that the session cookie does not show up in the process
+/// list.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Curl;
+
+impl Curl {
+    fn command(req: &Request) -> Command {
+        let mut cmd = Command::new("curl");
+        cmd.args(["--silent", "--show-error", "--location"])
+            .args(["--request", &req.method.to_string()])
+            // The status code is appended after the body, on its own line.
+            .args(["--write-out", "\n%{http_code}"])
+            .args(["--config", "-"])
+            .arg(&req.url)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        cmd
+    }
+
+    /// Returns the curl config with the headers and the body of `req`.
+    fn config(req: &Request) -> String {
+        let mut config = String::new();
+        for (name, value) in &req.headers {
+            config += &format!("header = {}\n", quote(&format!("{name}: {value}")));
+        }
+        if let Some(body) = &req.body {
+            config += &format!("data-binary = {}\n", quote(body));
+        }
+        config
+    }
+}
+
+/// Quotes a value of a curl config.
+fn quote(s: &str) -> String {
+    let mut res = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' | '\\' => {
+                res.push('\\');
+                res.push(c);
+            }
+            '\n' => res += "\\n",
+            '\r' => res += "\\r",
+            '\t' => res += "\\t",
+            c => res.push(c),
+        }
+    }
+    res.push('"');
+    res
+}
+
+impl Http for Curl {
+    fn send(&self, req: &Request) -> Result<Response, String> {
+        let mut cmd = Self::command(req);
+        let mut child = cmd
+            .spawn()
+            .map_err(|e| format!("failed running curl: {e}"))?;
+        let mut stdin = child.stdin.take().expect("piped stdin");
+        stdin
+            .write_all(Self::config(req).as_bytes())
+            .map_err(|e| format!("failed sending request to curl: {e}"))?;
+        drop(stdin);
+
+        let out = child
+            .wait_with_output()
+            .map_err(|e| format!("failed running curl: {e}"))?;
+        if !out.status.success() {
+            return Err(format!(
+                "{} {} failed: {}",
+                req.method,
+                req.url,
+                String::from_utf8_lossy(&out.stderr).trim()
+            ));
+        }
+
+        let out = String::from_utf8_lossy(&out.stdout);
+        let (body, status) = out.rsplit_once('\n').ok_or("unexpected curl output")?;
+        Ok(Response {
+            status: status
+                .trim()
+                .parse()
+                .map_err(|_| format!("unexpected status code '{status}'"))?,
+            body: body.to_owned(),
+        })
+    }
+}
+
+/// In-memory stand-in for the website, answering requests with canned
+/// responses by method and URL and recording every request it receives.
+#[cfg(test)]
+#[derive(Default)]
+pub struct FakeServer {
+    routes: Vec<(Method, String, Response)>,
+    pub requests: std::cell::RefCell<Vec<Request>>,
+}
+
+#[cfg(test)]
+impl FakeServer {
+    pub fn route(mut self, method: Method, url: &str, status: u16, body: &str) -> Self {
+        let res = Response {
+            status,
+            body: body.into(),
+        };
+        self.routes.push((method, url.into(), res));
+        self
+    }
+}
+
+#[cfg(test)]
+impl Http for FakeServer {
+    fn send(&self, req: &Request) -> Result<Response, String> {
+        self.requests.borrow_mut().push(req.clone());
+        Ok(self
+            .routes
+            .iter()
+            .find(|(m, url, _)| *m == req.method && *url == req.url)
+            .map(|(_, _, res)| res.clone())
+            .unwrap_or(Response {
+                status: 404,
+                body: "404 Not Found".into(),
+            }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_form() {
+        assert_eq!(url_encode("a-Z_0.~"), "a-Z_0.~");
+        assert_eq!(url_encode("1 + 1 = 2/ä"), "1+%2B+1+%3D+2%2F%C3%A4");
+
+        let req =
+            Request::new(Method::Post, "http://aoc").form(&[("level", "1"), ("answer", "a&b")]);
+        assert_eq!(req.body.as_deref(), Some("level=1&answer=a%26b"));
+        assert_eq!(
+            req.headers,
+            [(
+                "Content-Type".into(),
+                "application/x-www-form-urlencoded".into()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_curl_keeps_secrets_off_the_command_line() {
+        let req = Request::new(Method::Post, "https://adventofcode.com/2024/day/1/answer")
+            .header("Cookie", "session=secret-token")
+            .form(&[("answer", "say \"hi\"")]);
+
+        let cmd = Curl::command(&req);
+        let args: Vec<_> = cmd.get_args().map(|a| a.to_string_lossy()).collect();
+        assert!(args.iter().all(|a| !a.contains("secret-token")), "{args:?}");
+        assert!(args.iter().all(|a| !a.contains("answer=")), "{args:?}");
+        assert_eq!(args.last().unwrap(), &req.url);
+
+        assert_eq!(
+            Curl::config(&req),
+            "header = \"Cookie: session=secret-token\"\n\
+             header = \"Content-Type: application/x-www-form-urlencoded\"\n\
+             data-binary = \"answer=say+%22hi%22\"\n"
+        );
+        assert_eq!(quote("a\"b\\c\nd"), r#""a\"b\\c\nd""#);
+    }
+}
@@ -0,0 +1,427 @@
+//! Minimal JSON document model with a parser and a compact serializer.
+
+use core::fmt;
+use std::{error::Error, str::FromStr};
+
+/// A JSON value. Object members keep their order.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// Returns the value of member `key` if this is an object containing it.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.as_object()?
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v)
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(a) => Some(a),
+            _ => None,
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&[(String, Value)]> {
+        match self {
+            Value::Object(o) => Some(o),
+            _ => None,
+        }
+    }
+}
+
+/// Creates an object from `(key, value)` pairs.
+pub fn object<'a>(members: impl IntoIterator<Item = (&'a str, Value)>) -> Value {
+    Value::Object(
+        members
+            .into_iter()
+            .map(|(k, v)| (k.to_owned(), v))
+            .collect(),
+    )
+}
+
+impl From<bool> for Value {
+    fn from(v: bool) -> Self {
+        Value::Bool(v)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(v: f64) -> Self {
+        Value::Number(v)
+    }
+}
+
+macro_rules! from_int {
+    ($($t:ty),*) => {$(
+        impl From<$t> for Value {
+            fn from(v: $t) -> Self {
+                Value::Number(v as f64)
+            }
+        }
+    )*};
+}
+
+from_int!(u8, u32, u64, usize, i32, i64);
+
+impl From<&str> for Value {
+    fn from(v: &str) -> Self {
+        Value::String(v.to_owned())
+    }
+}
+
+impl From<String> for Value {
+    fn from(v: String) -> Self {
+        Value::String(v)
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(v: Option<T>) -> Self {
+        v.map_or(Value::Null, Into::into)
+    }
+}
+
+impl<T: Into<Value>> From<Vec<T>> for Value {
+    fn from(v: Vec<T>) -> Self {
+        Value::Array(v.into_iter().map(Into::into).collect())
+    }
+}
+
+impl fmt::Display for Value {
+    /// Serializes the value as compact JSON.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Null => write!(f, "null"),
+            Value::Bool(b) => write!(f, "{b}"),
+            Value::Number(n) if !n.is_finite() => write!(f, "null"),
+            // Integers up to 2^53 are exact, print them without fraction.
+            Value::Number(n) if n.fract() == 0.0 && n.abs() < 9e15 => write!(f, "{}", *n as i64),
+            Value::Number(n) => write!(f, "{n}"),
+            Value::String(s) => write_string(f, s),
+            Value::Array(a) => {
+                write!(f, "[")?;
+                for (i, v) in a.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{v}")?;
+                }
+                write!(f, "]")
+            }
+            Value::Object(o) => {
+                write!(f, "{{")?;
+                for (i, (k, v)) in o.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, k)?;
+                    write!(f, ":{v}")?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{c}")?,
+        }
+    }
+    write!(f, "\"")
+}
+
+/// Error returned when parsing invalid JSON.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonError {
+    /// Byte offset in the input where the error occurred.
+    pub pos: usize,
+    pub msg: String,
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid JSON at byte {}: {}", self.pos, self.msg)
+    }
+}
+
+impl Error for JsonError {}
+
+impl FromStr for Value {
+    type Err = JsonError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser { s, pos: 0 };
+        let v = parser.value()?;
+        parser.skip_ws();
+        if parser.pos < s.len() {
+            return Err(parser.err("trailing characters"));
+        }
+        Ok(v)
+    }
+}
+
+struct Parser<'a> {
+    s: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn err(&self, msg: &str) -> JsonError {
+        JsonError {
+            pos: self.pos,
+            msg: msg.into(),
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.s.as_bytes().get(self.pos).copied()
+    }
+
+    fn skip_ws(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, lit: &str) -> Result<(), JsonError> {
+        if self.s[self.pos..].starts_with(lit) {
+            self.pos += lit.len();
+            Ok(())
+        } else {
+            Err(self.err(&format!("expected '{lit}'")))
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, JsonError> {
+        self.skip_ws();
+        match self.peek() {
+            Some(b'n') => self.expect("null").map(|_| Value::Null),
+            Some(b't') => self.expect("true").map(|_| Value::Bool(true)),
+            Some(b'f') => self.expect("false").map(|_| Value::Bool(false)),
+            Some(b'"') => self.string().map(Value::String),
+            Some(b'[') => self.array(),
+            Some(b'{') => self.object(),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            Some(_) => Err(self.err("unexpected character")),
+            None => Err(self.err("unexpected end of input")),
+        }
+    }
+
+    fn number(&mut self) -> Result<Value, JsonError> {
+        let start = self.pos;
+        while matches!(
+            self.peek(),
+            Some(b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9')
+        ) {
+            self.pos += 1;
+        }
+        self.s[start..self.pos]
+            .parse()
+            .map(Value::Number)
+            .map_err(|_| JsonError {
+                pos: start,
+                msg: "invalid number".into(),
+            })
+    }
+
+    fn string(&mut self) -> Result<String, JsonError> {
+        self.expect("\"")?;
+        let mut res = String::new();
+        loop {
+            let rest = &self.s[self.pos..];
+            let Some(c) = rest.chars().next() else {
+                return Err(self.err("unterminated string"));
+            };
+            self.pos += c.len_utf8();
+            match c {
+                '"' => return Ok(res),
+                '\\' => {
+                    let escaped = match self.peek() {
+                        Some(b'"') => '"',
+                        Some(b'\\') => '\\',
+                        Some(b'/') => '/',
+                        Some(b'n') => '\n',
+                        Some(b'r') => '\r',
+                        Some(b't') => '\t',
+                        Some(b'b') => '\u{8}',
+                        Some(b'f') => '\u{c}',
+                        Some(b'u') => self.unicode_escape()?,
+                        _ => return Err(self.err("invalid escape")),
+                    };
+                    self.pos += 1;
+                    res.push(escaped);
+                }
+                c => res.push(c),
+            }
+        }
+    }
+
+    /// Parses the `uXXXX` of a unicode escape, which is followed by the
+    /// escape of the low surrogate for characters outside of the basic
+    /// multilingual plane. Leaves the position on the last hex digit.
+    fn unicode_escape(&mut self) -> Result<char, JsonError> {
+        let hex = |at: usize| {
+            self.s
+                .get(at..at + 4)
+                .filter(|h| h.bytes().all(|b| b.is_ascii_hexdigit()))
+                .and_then(|h| u32::from_str_radix(h, 16).ok())
+        };
+
+        let high = hex(self.pos + 1).ok_or_else(|| self.err("invalid unicode escape"))?;
+        self.pos += 4;
+        let code = match high {
+            0xd800..=0xdbff => {
+                let low = self.s[self.pos + 1..]
+                    .strip_prefix("\\u")
+                    .and_then(|_| hex(self.pos + 3))
+                    .filter(|low| (0xdc00..=0xdfff).contains(low))
+                    .ok_or_else(|| self.err("unpaired surrogate in unicode escape"))?;
+                self.pos += 6;
+                0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+            }
+            0xdc00..=0xdfff => return Err(self.err("unpaired surrogate in unicode escape")),
+            code => code,
+        };
+        char::from_u32(code).ok_or_else(|| self.err("invalid unicode escape"))
+    }
+
+    fn array(&mut self) -> Result<Value, JsonError> {
+        self.expect("[")?;
+        let mut res = vec![];
+        self.skip_ws();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(Value::Array(res));
+        }
+        loop {
+            res.push(self.value()?);
+            self.skip_ws();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(Value::Array(res));
+                }
+                _ => return Err(self.err("expected ',' or ']'")),
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Value, JsonError> {
+        self.expect("{")?;
+        let mut res = vec![];
+        self.skip_ws();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(Value::Object(res));
+        }
+        loop {
+            self.skip_ws();
+            let key = self.string()?;
+            self.skip_ws();
+            self.expect(":")?;
+            res.push((key, self.value()?));
+            self.skip_ws();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(Value::Object(res));
+                }
+                _ => return Err(self.err("expected ',' or '}'")),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let s = r#"{"a":[1,-2.5,1e-7,true,false,null],"b":"x\"y\\z\n\u0001","c":{}}"#;
+        let v: Value = s.parse().unwrap();
+        assert_eq!(v.to_string(), s.replace("1e-7", "0.0000001"));
+        assert_eq!(v.to_string().parse::<Value>().unwrap(), v);
+    }
+
+    #[test]
+    fn test_whitespace_and_unicode() {
+        let v: Value = " { \"k\" : [ \"\\u00e4ö\" ] } ".parse().unwrap();
+        assert_eq!(
+            v.get("k").unwrap().as_array().unwrap()[0].as_str(),
+            Some("äö")
+        );
+    }
+
+    #[test]
+    fn test_surrogate_pairs() {
+        let v: Value = r#""\ud83d\ude00!""#.parse().unwrap();
+        assert_eq!(v.as_str(), Some("😀!"));
+        assert_eq!(v.to_string(), "\"😀!\"");
+
+        let err = |s: &str| s.parse::<Value>().unwrap_err().msg;
+        assert_eq!(err(r#""\ud83d""#), "unpaired surrogate in unicode escape");
+        assert_eq!(err(r#""\ud83dx""#), "unpaired surrogate in unicode escape");
+        assert_eq!(
+            err(r#""\ud83d\u0041""#),
+            "unpaired surrogate in unicode escape"
+        );
+        assert_eq!(err(r#""\ude00""#), "unpaired surrogate in unicode escape");
+        assert_eq!(err(r#""\u12g4""#), "invalid unicode escape");
+    }
+
+    #[test]
+    fn test_get_and_object() {
+        let v: Value = r#"{"a": {"b": [1, 2.5]}}"#.parse().unwrap();
+        let b = v.get("a").and_then(|a| a.get("b")).unwrap();
+        assert_eq!(b.as_array().unwrap()[1].as_f64(), Some(2.5));
+        assert_eq!(v.get("c"), None);
+
+        let v = object([("day", 1.into()), ("answer", "42".into())]);
+        assert_eq!(v.to_string(), r#"{"day":1,"answer":"42"}"#);
+    }
+
+    #[test]
+    fn test_errors() {
+        let err = |s: &str| s.parse::<Value>().unwrap_err();
+        assert_eq!(err("[1,]").pos, 3);
+        assert_eq!(err("{\"a\" 1}").msg, "expected ':'");
+        assert_eq!(err("\"abc").msg, "unterminated string");
+        assert_eq!(err("1 2").msg, "trailing characters");
+        assert_eq!(err("").msg, "unexpected end of input");
+    }
+}
@@ -0,0 +1,374 @@
+mod bench;
+mod check;
+mod client;
+mod examples;
+mod fetch;
+mod html;
+mod http;
+mod json;
+mod new;
+mod run;
+mod submit;
+
+use run::Format;
+
+use lib::{Day, InputLocation, InputSource, Part, bench::measure};
+use std::{
+    any::Any,
+    env,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::Duration,
+};
+
+/// Solutions of all days. New days are registered here.
+const DAYS: &[fn() -> Day] = &[
+    || Day::new::<day_01::Day01>(1),
+    || Day::new::<day_02::Day02>(2),
+    || Day::new::<day_03::Day03>(3),
+    || Day::new::<day_04::Day04>(4),
+    || Day::new::<day_05::Day05>(5),
+    || Day::new::<day_06::Day06>(6),
+    || Day::new::<day_07::Day07>(7),
+    || Day::new::<day_08::Day08>(8),
+    || Day::new::<day_09::Day09>(9),
+    || Day::new::<day_10::Day10>(10),
+    || Day::new::<day_11::Day11>(11),
+    || Day::new::<day_12::Day12>(12),
+];
+
+const USAGE: &str = "\
+Usage: aoc new [<day>]
+       aoc fetch [<day>|all]
+       aoc run [<day>|all] [--part <1|2>] [--test] [--input <path>] [--verify]
+               [--format <plain|json|csv>]
+       aoc examples <day> [--puzzle <path>] [--example <n>] [--example2 <n>]
+                    [--force]
+       aoc submit <day> <part>
+       aoc bench [<day>|all] [--part <1|2>] [--test] [--input <path>]
+                 [--iterations <n>] [--warmup <n>] [--threshold <percent>]
+                 [--history <path>]
+       aoc check [<day>|all] [--budget <ms>]
+
+`new` creates the project of the given day, or of the day after the latest
+one, and registers it in the workspace and in this runner. It then fetches
+the input of the day like `fetch` does.
+
+`fetch` downloads the puzzle input of the given day, all days or, if no day is
+given, the latest day into `day-XX/input.txt`. Existing inputs are kept. The
+session token is read from the `SESSION_TOKEN` environment variable or the
+`.env` file in the workspace, the year from `YEAR` (default 2024). Inputs are
+cached in `.cache/inputs` and requests are throttled to one every 5 seconds.
+
+`run` runs the solution of the given day, all days or, if no day is given, the
+latest day and shows how long parsing and each part took.
+
+By default, the puzzle input `day-XX/input.txt` is used. `--test` uses the
+example input `day-XX/test_input.txt`, or `day-XX/test_input_N.txt` for part N
+if it exists. `--input` reads the input from the given file or, for `-`, from
+stdin.
+
+Answers are compared to the expected ones in `day-XX/answers.toml` and marked
+with ✓, ✗ or, without an expected answer, as unverified. With `--verify`, the
+run fails if any answer does not match or is unverified.
+
+`--format json` prints all answers as a single JSON array and `--format csv`
+prints one line per answer. Both contain the day, part, input, answer, parse and
+solve durations in nanoseconds and the verification status.
+
+`examples` extracts the `<pre><code>` blocks and the emphasised answers from the
+puzzle page saved as `day-XX/puzzle.html`, or the file given by `--puzzle`. The
+first example of part 1, or example `--example`, is written to
+`day-XX/test_input.txt` and example `--example2` to `day-XX/test_input_2.txt`.
+The last emphasised answer of each part is added to the `[example]` table of
+`day-XX/answers.toml`. Existing inputs and answers are only replaced with
+`--force`.
+
+`submit` computes the answer of the given part for the puzzle input and submits
+it. Every attempt is recorded in `.cache/attempts`, so that rejected answers,
+answers outside of the bounds given by earlier too high or too low answers and
+answers within the cooldown of the last attempt are not submitted again.
+
+`bench` runs parsing and each part `--iterations` times (default 100) after
+`--warmup` runs (default 10). The results are stored in `bench_history.json`
+in the workspace, or the file given by `--history`, and compared to the last
+stored results. Phases whose median got slower by more than `--threshold`
+percent (default 10) are flagged.
+
+`check` verifies that the given day, all days or, if no day is given, the
+latest day are ready to be committed: no `TODO` or `todo!()` is left in the
+sources, the tests pass, the answers for the example and the puzzle input match
+the ones in `answers.toml` and parsing and solving both parts of the puzzle
+input takes less than `--budget` milliseconds (default 1000). It fails if any
+check fails and leaves committing to you.";
+
+/// Which days to run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Selection {
+    Latest,
+    All,
+    Day(u8),
+}
+
+/// Arguments of the `run`, `bench` and `check` commands. Flags which only
+/// apply to some of them are ignored by the others.
+#[derive(Debug)]
+struct Args {
+    selection: Selection,
+    part: Option<Part>,
+    source: InputSource,
+    verify: bool,
+    format: Format,
+    iterations: usize,
+    warmup: usize,
+    threshold: f64,
+    history: Option<PathBuf>,
+    budget: Duration,
+}
+
+impl Args {
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let args: Vec<_> = args.into_iter().collect();
+
+        let mut res = Self {
+            selection: Selection::Latest,
+            part: None,
+            source: InputSource::from_args(args.iter().cloned()).map_err(|e| e.to_string())?,
+            verify: false,
+            format: Format::default(),
+            iterations: 100,
+            warmup: 10,
+            threshold: 10.0,
+            history: None,
+            budget: Duration::from_secs(1),
+        };
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or_else(|| format!("{arg} requires a value"));
+            let invalid = |v: &str| format!("invalid value '{v}' for {arg}");
+
+            match arg.as_str() {
+                // The input source has been read from the arguments above.
+                "--test" => {}
+                "--input" => {
+                    value()?;
+                }
+                "--verify" => res.verify = true,
+                "--format" => res.format = value()?.parse()?,
+                "--part" => res.part = Some(value()?.parse()?),
+                "--iterations" => {
+                    let v = value()?;
+                    res.iterations = v.parse().ok().filter(|&n| n > 0).ok_or(invalid(&v))?;
+                }
+                "--warmup" => {
+                    let v = value()?;
+                    res.warmup = v.parse().map_err(|_| invalid(&v))?;
+                }
+                "--threshold" => {
+                    let v = value()?;
+                    res.threshold = v.parse().map_err(|_| invalid(&v))?;
+                }
+                "--history" => res.history = Some(value()?.into()),
+                "--budget" => {
+                    let v = value()?;
+                    res.budget = Duration::from_millis(v.parse().map_err(|_| invalid(&v))?);
+                }
+                "all" => res.selection = Selection::All,
+                _ if arg.starts_with('-') => return Err(format!("unknown flag '{arg}'")),
+                _ => {
+                    let day = arg.parse().map_err(|_| format!("invalid day '{arg}'"))?;
+                    res.selection = Selection::Day(day);
+                }
+            }
+        }
+
+        if res.selection == Selection::All
+            && matches!(res.source, InputSource::Path(_) | InputSource::Stdin)
+        {
+            return Err("--input can only be used with a single day".into());
+        }
+
+        Ok(res)
+    }
+
+    /// Returns the selected days.
+    fn days(&self) -> Result<Vec<Day>, String> {
+        let mut days = DAYS.iter().map(|d| d());
+        match self.selection {
+            Selection::All => Ok(days.collect()),
+            Selection::Latest => Ok(days.next_back().into_iter().collect()),
+            Selection::Day(day) => days
+                .filter(|d| d.day == day)
+                .map(|d| vec![d])
+                .next()
+                .ok_or_else(|| format!("day {day} has no solution")),
+        }
+    }
+
+    /// Returns the selected parts.
+    fn parts(&self) -> Vec<Part> {
+        match self.part {
+            Some(part) => vec![part],
+            None => Part::all().to_vec(),
+        }
+    }
+}
+
+/// Parses the day and part given to the `submit` command.
+fn parse_submit_args(mut args: impl Iterator<Item = String>) -> Result<(u8, Part), String> {
+    let (Some(day), Some(part), None) = (args.next(), args.next(), args.next()) else {
+        return Err("expected a day and a part".into());
+    };
+    let day = day.parse().map_err(|_| format!("invalid day '{day}'"))?;
+    Ok((day, part.parse()?))
+}
+
+fn main() -> ExitCode {
+    let mut args = env::args().skip(1);
+
+    let res = match args.next().as_deref() {
+        Some("run") => Args::parse(args).and_then(|args| run::run(&args)),
+        Some("new") => args
+            .next()
+            .map(|day| day.parse().map_err(|_| format!("invalid day '{day}'")))
+            .transpose()
+            .and_then(new::new),
+        Some("fetch") => Args::parse(args).and_then(|args| fetch::fetch(&args)),
+        Some("examples") => {
+            examples::ExamplesArgs::parse(args).and_then(|args| examples::examples(&args))
+        }
+        Some("submit") => parse_submit_args(args).and_then(|(day, part)| submit::submit(day, part)),
+        Some("bench") => Args::parse(args).and_then(|args| bench::bench(&args)),
+        Some("check") => Args::parse(args).and_then(|args| check::check(&args)),
+        Some("help" | "--help" | "-h") => {
+            println!("{USAGE}");
+            Ok(())
+        }
+        Some(cmd) => Err(format!("unknown command '{cmd}'\n\n{USAGE}")),
+        None => Err(USAGE.into()),
+    };
+
+    match res {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Prints a header for the `i`th of `count` days if more than one day is
+/// run.
+fn print_header(i: usize, count: usize, day: &Day) {
+    if count > 1 {
+        if i > 0 {
+            println!();
+        }
+        println!("----- Day {:02} -----", day.day);
+    }
+}
+
+/// Input of a day parsed for one or more parts.
+struct Parsed {
+    location: InputLocation,
+    raw: String,
+    input: Box<dyn Any>,
+    duration: Duration,
+}
+
+impl Parsed {
+    /// Reads and parses the input at `location`.
+    fn new(day: &Day, location: InputLocation) -> Result<Self, String> {
+        let raw = location.read().map_err(|e| e.to_string())?;
+        let (input, duration) = measure(|| day.parse(&raw));
+        Ok(Self {
+            location,
+            raw,
+            input,
+            duration,
+        })
+    }
+
+    /// Returns the input for `part`, reusing `prev` if it has been parsed
+    /// from the same location. Parts might use different example inputs,
+    /// but consecutive parts using the same input share the parsed result.
+    /// The flag is true if the input has been parsed freshly.
+    fn for_part(
+        prev: Option<Self>,
+        day: &Day,
+        source: &InputSource,
+        part: Part,
+    ) -> Result<(Self, bool), String> {
+        let location = source.locate(day_dir(day.day), part);
+        match prev {
+            Some(prev) if prev.location == location => Ok((prev, false)),
+            _ => Ok((Self::new(day, location)?, true)),
+        }
+    }
+}
+
+/// Returns the root directory of the workspace.
+fn workspace_dir() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("aoc crate is part of the workspace")
+}
+
+/// Returns the project directory of the given day.
+fn day_dir(day: u8) -> PathBuf {
+    workspace_dir().join(format!("day-{day:02}"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, String> {
+        Args::parse(args.iter().map(|a| a.to_string()))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args = parse(&["3", "--part", "2", "--test"]).unwrap();
+        assert_eq!(args.selection, Selection::Day(3));
+        assert_eq!(args.part, Some(Part::Two));
+        assert_eq!(args.source, InputSource::Example);
+        assert!(!args.verify);
+        assert!(parse(&["all", "--verify"]).unwrap().verify);
+        assert_eq!(
+            parse(&["11", "--counts"]).unwrap_err(),
+            "unknown flag '--counts'"
+        );
+
+        let args = parse(&["--input", "12", "7"]).unwrap();
+        assert_eq!(args.selection, Selection::Day(7));
+        assert_eq!(args.source, InputSource::Path("12".into()));
+        assert!(parse(&["all", "--input", "-"]).is_err());
+
+        assert_eq!(parse(&[]).unwrap().selection, Selection::Latest);
+        assert_eq!(parse(&["all"]).unwrap().selection, Selection::All);
+
+        assert!(parse(&["--part", "3"]).is_err());
+        assert!(parse(&["--part"]).is_err());
+        assert!(parse(&["seven"]).is_err());
+
+        assert_eq!(parse(&["--format", "csv"]).unwrap().format, Format::Csv);
+        assert!(parse(&["--format", "xml"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_bench_args() {
+        let args = parse(&["5", "--iterations", "20", "--threshold", "2.5"]).unwrap();
+        assert_eq!(args.iterations, 20);
+        assert_eq!(args.warmup, 10);
+        assert_eq!(args.threshold, 2.5);
+
+        assert!(parse(&["--iterations", "0"]).is_err());
+        assert!(parse(&["--warmup", "x"]).is_err());
+
+        let args = parse(&["all", "--budget", "250"]).unwrap();
+        assert_eq!(args.budget, Duration::from_millis(250));
+        assert!(parse(&["--budget", "1.5"]).is_err());
+    }
+}
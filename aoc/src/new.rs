@@ -0,0 +1,870 @@
+use crate::{DAYS, fetch, workspace_dir};
+use std::{fs, path::Path};
+
+/// Creates the project of `day`, or of the day after the latest existing one
+/// if no day is given, and registers it in the workspace and the runner.
+pub fn new(day: Option<u8>) -> Result<(), String> {
+    let root = workspace_dir();
+    let day = match day {
+        Some(day) => day,
+        None => DAYS.iter().map(|d| d().day).max().unwrap_or_default() + 1,
+    };
+
+    scaffold(root, day)?;
+
+    println!("Created day-{day:02}");
+
+    // The project is usable without the input, so it is only fetched on a
+    // best effort basis.
+    if let Err(err) = fetch::fetch_input(&fetch::client(), day) {
+        eprintln!("warning: failed fetching the input: {err}");
+        eprintln!("fetch it later with `aoc fetch {day}`");
+    }
+    Ok(())
+}
+
+/// Creates the project of `day` in the workspace at `root`. All edits are
+/// prepared before anything is written, so that nothing is changed if any
+/// of them fails.
+fn scaffold(root: &Path, day: u8) -> Result<(), String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("invalid day {day}, expected 1 to 25"));
+    }
+
+    let name = format!("day-{day:02}");
+    let dir = root.join(&name);
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()));
+    }
+
+    let read = |path: &Path| {
+        fs::read_to_string(path).map_err(|e| format!("failed reading {}: {e}", path.display()))
+    };
+
+    let workspace_manifest = root.join("Cargo.toml");
+    let runner_manifest = root.join("aoc/Cargo.toml");
+    let runner_main = root.join("aoc/src/main.rs");
+
+    let edits = [
+        (
+            &workspace_manifest,
+            add_member(&read(&workspace_manifest)?, &name)?,
+        ),
+        (
+            &runner_manifest,
+            add_dependency(&read(&runner_manifest)?, &name)?,
+        ),
+        (&runner_main, register_day(&read(&runner_main)?, day)?),
+    ];
+
+    let files = [
+        ("Cargo.toml", manifest_template(&name)),
+        ("src/lib.rs", lib_template(day)),
+        ("test_input.txt", String::new()),
+    ];
+
+    fs::create_dir_all(dir.join("src"))
+        .map_err(|e| format!("failed creating {}: {e}", dir.display()))?;
+    for (file, content) in files {
+        let path = dir.join(file);
+        fs::write(&path, content).map_err(|e| format!("failed writing {}: {e}", path.display()))?;
+    }
+    for (path, content) in edits {
+        fs::write(path, content).map_err(|e| format!("failed writing {}: {e}", path.display()))?;
+    }
+
+    Ok(())
+}
+
+fn manifest_template(name: &str) -> String {
+    format!(
+        r#"[package]
+name = "{name}"
+version = "0.1.0"
+edition = "2024"
+
+[dependencies]
+lib = {{ version = "0.1.0", path = "../lib" }}
+"#
+    )
+}
+
+fn lib_template(day: u8) -> String {
+    format!(
+        r#"use lib::*;
+use std::fmt::Display;
+
+pub struct Day{day:02};
+
+impl Solution for Day{day:02} {{
+    type Input = String;
+
+    fn parse(input: &str) -> Self::Input {{
+        input.to_owned()
+    }}
+
+    fn part1(_input: &Self::Input) -> impl Display {{
+        "TODO: part 1"
+    }}
+
+    fn part2(_input: &Self::Input) -> impl Display {{
+        "TODO: part 2"
+    }}
+}}
+
+#[cfg(test)]
+mod test {{
+    use super::*;
+
+    const INPUT: &str = include_str!("../test_input.txt");
+
+    #[test]
+    #[ignore = "TODO: add the expected answer"]
+    fn test_part1() {{
+        let input = Day{day:02}::parse(INPUT.trim_end());
+        assert_eq!(Day{day:02}::part1(&input).to_string(), "");
+    }}
+
+    #[test]
+    #[ignore = "TODO: add the expected answer"]
+    fn test_part2() {{
+        let input = Day{day:02}::parse(INPUT.trim_end());
+        assert_eq!(Day{day:02}::part2(&input).to_string(), "");
+    }}
+}}
+"#
+    )
+}
+
+/// Returns the index at which an entry with the sort key `new_key` is
+/// inserted among entries with the given `keys`. Among the `day-XX` entries,
+/// it is placed before the first one with a greater key, or after the last
+/// one. Without any day entries, it is placed at the end.
+fn insert_position<'a>(
+    keys: impl IntoIterator<Item = Option<&'a str>>,
+    new_key: &str,
+) -> Result<usize, String> {
+    let mut len = 0;
+    let mut insert_at = None;
+    let mut last_day = None;
+
+    for (i, k) in keys.into_iter().enumerate() {
+        len = i + 1;
+        let Some(k) = k else { continue };
+        if k == new_key {
+            return Err(format!("{new_key} is already registered"));
+        }
+        if k.starts_with("day-") {
+            last_day = Some(i);
+            if insert_at.is_none() && k > new_key {
+                insert_at = Some(i);
+            }
+        }
+    }
+
+    Ok(insert_at.or(last_day.map(|i| i + 1)).unwrap_or(len))
+}
+
+/// An element of an array with its byte range.
+#[derive(Debug)]
+struct Element {
+    /// The string value, or the sort key of a `DAYS` entry.
+    value: String,
+    start: usize,
+    end: usize,
+    /// Whether a comma follows the element.
+    comma: bool,
+}
+
+/// An array with the byte offsets of its brackets.
+#[derive(Debug)]
+struct Array {
+    open: usize,
+    close: usize,
+    elements: Vec<Element>,
+}
+
+/// Parses the comma-separated list that opens at byte `open` of `s` and ends
+/// with `close`, which may contain a trailing comma. `skip` skips whitespace
+/// and comments from a byte offset, and `element` parses an element at a byte
+/// offset into its value and end.
+fn parse_list(
+    s: &str,
+    open: usize,
+    close: u8,
+    skip: impl Fn(usize) -> usize,
+    element: impl Fn(usize) -> Result<(String, usize), String>,
+) -> Result<Array, String> {
+    let bytes = s.as_bytes();
+    let mut elements = vec![];
+    let mut pos = open;
+
+    loop {
+        pos = skip(pos + 1);
+        match bytes.get(pos) {
+            Some(&b) if b == close => break,
+            None => return Err(format!("missing '{}'", close as char)),
+            _ => {}
+        }
+
+        let start = pos;
+        let (value, end) = element(pos)?;
+        pos = skip(end);
+        let comma = bytes.get(pos) == Some(&b',');
+        elements.push(Element {
+            value,
+            start,
+            end,
+            comma,
+        });
+        if !comma {
+            if bytes.get(pos) != Some(&close) {
+                return Err(format!("expected ',' or '{}'", close as char));
+            }
+            break;
+        }
+    }
+
+    Ok(Array {
+        open,
+        close: pos,
+        elements,
+    })
+}
+
+/// Skips whitespace and comments of a TOML document from byte `pos`, across
+/// lines only if `newlines` is set.
+fn skip_toml_space(s: &str, mut pos: usize, newlines: bool) -> usize {
+    loop {
+        match s.as_bytes().get(pos) {
+            Some(b' ' | b'\t' | b'\r') => pos += 1,
+            Some(b'\n') if newlines => pos += 1,
+            Some(b'#') => pos = s[pos..].find('\n').map_or(s.len(), |i| pos + i),
+            _ => return pos,
+        }
+    }
+}
+
+/// Parses the TOML string at byte `pos` of `s` into its contents and end.
+/// Escape sequences are replaced by the escaped character.
+fn parse_string(s: &str, pos: usize) -> Result<(String, usize), String> {
+    let rest = &s[pos..];
+    for delim in ["\"\"\"", "'''"] {
+        if let Some(body) = rest.strip_prefix(delim) {
+            let len = body.find(delim).ok_or("unclosed string")?;
+            return Ok((body[..len].into(), pos + len + 2 * delim.len()));
+        }
+    }
+
+    let mut chars = rest.char_indices();
+    let quote = match chars.next() {
+        Some((_, c @ ('"' | '\''))) => c,
+        _ => return Err("expected a string".into()),
+    };
+    let mut value = String::new();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\n' => break,
+            '\\' if quote == '"' => {
+                let (_, c) = chars.next().ok_or("unclosed string")?;
+                value.push(c);
+            }
+            c if c == quote => return Ok((value, pos + i + 1)),
+            c => value.push(c),
+        }
+    }
+    Err("unclosed string".into())
+}
+
+/// Parses the possibly dotted TOML key at byte `pos` of `s` into its parts
+/// and the position after the whitespace following it.
+fn parse_key(s: &str, mut pos: usize) -> Result<(Vec<String>, usize), String> {
+    let mut keys = vec![];
+    loop {
+        pos = skip_toml_space(s, pos, false);
+        if s[pos..].starts_with(['"', '\'']) {
+            let (key, end) = parse_string(s, pos)?;
+            keys.push(key);
+            pos = end;
+        } else {
+            let len = s[pos..]
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+                .unwrap_or(s.len() - pos);
+            if len == 0 {
+                return Err("expected a key".into());
+            }
+            keys.push(s[pos..pos + len].into());
+            pos += len;
+        }
+
+        pos = skip_toml_space(s, pos, false);
+        if !s[pos..].starts_with('.') {
+            return Ok((keys, pos));
+        }
+        pos += 1;
+    }
+}
+
+/// Returns the end of the TOML value at byte `pos` of `s`.
+fn skip_value(s: &str, pos: usize) -> Result<usize, String> {
+    let skip = |pos| skip_toml_space(s, pos, true);
+    match s.as_bytes().get(pos) {
+        Some(b'"' | b'\'') => parse_string(s, pos).map(|(_, end)| end),
+        Some(b'[') => {
+            let array = parse_list(s, pos, b']', skip, |pos| {
+                Ok((String::new(), skip_value(s, pos)?))
+            })?;
+            Ok(array.close + 1)
+        }
+        Some(b'{') => {
+            let table = parse_list(s, pos, b'}', skip, |pos| {
+                let (_, end) = parse_key(s, pos)?;
+                if !s[end..].starts_with('=') {
+                    return Err("expected '='".into());
+                }
+                let value = skip_toml_space(s, end + 1, false);
+                Ok((String::new(), skip_value(s, value)?))
+            })?;
+            Ok(table.close + 1)
+        }
+        _ => {
+            let len = s[pos..]
+                .find([',', ']', '}', '#', '\n'])
+                .unwrap_or(s.len() - pos);
+            match s[pos..pos + len].trim_end() {
+                "" => Err("expected a value".into()),
+                value => Ok(pos + value.len()),
+            }
+        }
+    }
+}
+
+/// A table header or key/value pair of a TOML document.
+#[derive(Debug)]
+struct Item {
+    /// The key of a header, or the key of a pair including its table.
+    path: Vec<String>,
+    header: bool,
+    /// Byte offset of the item, and of the value of a pair.
+    start: usize,
+    value: usize,
+    /// Byte offset after the end of the line of the item.
+    line_end: usize,
+}
+
+/// Splits the TOML document `s` into its table headers and key/value pairs.
+fn parse_toml(s: &str) -> Result<Vec<Item>, String> {
+    let mut items: Vec<Item> = vec![];
+    let mut pos = skip_toml_space(s, 0, true);
+
+    while pos < s.len() {
+        let table = match items.iter().rfind(|i| i.header) {
+            Some(header) => &header.path[..],
+            None => &[],
+        };
+        let item = parse_item(s, pos, table).map_err(|e| {
+            let line = s[..pos].matches('\n').count() + 1;
+            format!("line {line}: {e}")
+        })?;
+        pos = skip_toml_space(s, item.line_end, true);
+        items.push(item);
+    }
+    Ok(items)
+}
+
+/// Parses the header or key/value pair at byte `start` of `s`, where `table`
+/// is the key of the table it is in.
+fn parse_item(s: &str, start: usize, table: &[String]) -> Result<Item, String> {
+    let (path, header, value, end) = if s[start..].starts_with('[') {
+        let close = if s[start..].starts_with("[[") {
+            "]]"
+        } else {
+            "]"
+        };
+        let (keys, end) = parse_key(s, start + close.len())?;
+        if !s[end..].starts_with(close) {
+            return Err(format!("expected '{close}'"));
+        }
+        (keys, true, end, end + close.len())
+    } else {
+        let (keys, end) = parse_key(s, start)?;
+        if !s[end..].starts_with('=') {
+            return Err("expected '='".into());
+        }
+        let value = skip_toml_space(s, end + 1, false);
+        let path = table.iter().cloned().chain(keys).collect();
+        (path, false, value, skip_value(s, value)?)
+    };
+
+    let line_end = skip_toml_space(s, end, false);
+    let line_end = match s.as_bytes().get(line_end) {
+        Some(b'\n') => line_end + 1,
+        None => line_end,
+        _ => return Err("expected a new line".into()),
+    };
+    Ok(Item {
+        path,
+        header,
+        start,
+        value,
+        line_end,
+    })
+}
+
+/// Parses the array of strings starting at byte `pos` of `s`, which may span
+/// multiple lines and contain comments and a trailing comma.
+fn parse_array(s: &str, pos: usize) -> Result<Array, String> {
+    let open = skip_toml_space(s, pos, true);
+    if !s[open..].starts_with('[') {
+        return Err("expected an array".into());
+    }
+    parse_list(
+        s,
+        open,
+        b']',
+        |pos| skip_toml_space(s, pos, true),
+        |pos| parse_string(s, pos),
+    )
+}
+
+/// Skips whitespace and comments of Rust source from byte `pos`.
+fn skip_rust_space(s: &str, mut pos: usize) -> usize {
+    loop {
+        let rest = &s[pos..];
+        let trimmed = rest.trim_start();
+        pos += rest.len() - trimmed.len();
+        if trimmed.starts_with("//") {
+            pos += trimmed.find('\n').unwrap_or(trimmed.len());
+        } else if trimmed.starts_with("/*") {
+            pos += trimmed.find("*/").map_or(trimmed.len(), |i| i + 2);
+        } else {
+            return pos;
+        }
+    }
+}
+
+/// Parses the `DAYS` entry at byte `pos` of the runner source into the name
+/// of the day project it registers and its end. Entries that do not name a
+/// day crate are keyed by their source.
+fn parse_days_entry(s: &str, mut pos: usize) -> Result<(String, usize), String> {
+    let start = pos;
+    let mut end = pos;
+    let mut depth = 0usize;
+
+    while let Some(c) = s[pos..].chars().next() {
+        let next = skip_rust_space(s, pos);
+        if next != pos {
+            pos = next;
+            continue;
+        }
+        match c {
+            ',' | ']' if depth == 0 => break,
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth = depth.checked_sub(1).ok_or("unbalanced brackets")?,
+            _ => {}
+        }
+        pos += c.len_utf8();
+        end = pos;
+    }
+    if pos == s.len() {
+        return Err("missing ']'".into());
+    }
+    if end == start {
+        return Err("expected an entry".into());
+    }
+
+    let entry = &s[start..end];
+    let key = entry
+        .split(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .find(|w| {
+            w.strip_prefix("day_")
+                .is_some_and(|d| !d.is_empty() && d.bytes().all(|b| b.is_ascii_digit()))
+        })
+        .map_or_else(|| entry.into(), |w| w.replace('_', "-"));
+    Ok((key, end))
+}
+
+/// Returns the whitespace in front of byte `pos` of `s` if nothing else
+/// precedes it on its line.
+fn indentation(s: &str, pos: usize) -> Option<&str> {
+    let line_start = s[..pos].rfind('\n').map_or(0, |i| i + 1);
+    let indent = &s[line_start..pos];
+    indent.trim().is_empty().then_some(indent)
+}
+
+/// Inserts `text` as the element at index `at` of `array` in `s`, keeping
+/// the layout of the array.
+fn insert_element(s: &str, array: &Array, at: usize, text: &str) -> String {
+    // Edits as (byte offset, text), applied from the back.
+    let mut edits = vec![];
+    if let Some(next) = array.elements.get(at) {
+        match indentation(s, next.start) {
+            Some(indent) => edits.push((next.start, format!("{text},\n{indent}"))),
+            None => edits.push((next.start, format!("{text}, "))),
+        }
+    } else {
+        let last = array.elements.last();
+        match indentation(s, array.close) {
+            Some(close_indent) if s[array.open..array.close].contains('\n') => {
+                let indent = last.and_then(|l| indentation(s, l.start)).unwrap_or("    ");
+                if let Some(last) = last.filter(|l| !l.comma) {
+                    edits.push((last.end, ",".into()));
+                }
+                let close_line = array.close - close_indent.len();
+                edits.push((close_line, format!("{indent}{text},\n")));
+            }
+            _ => match last {
+                Some(last) => edits.push((last.end, format!(", {text}"))),
+                None => edits.push((array.open + 1, text.into())),
+            },
+        }
+    }
+
+    let mut res = s.to_string();
+    for (pos, text) in edits.into_iter().rev() {
+        res.insert_str(pos, &text);
+    }
+    res
+}
+
+/// Adds `member` to the `members` array of the `[workspace]` table, keeping
+/// the layout of the array.
+fn add_member(manifest: &str, member: &str) -> Result<String, String> {
+    let items = parse_toml(manifest).map_err(|e| format!("invalid workspace manifest: {e}"))?;
+    let members = items
+        .iter()
+        .find(|i| !i.header && i.path == ["workspace", "members"])
+        .ok_or("no workspace members found")?;
+    let array = parse_array(manifest, members.value)
+        .map_err(|e| format!("invalid workspace members: {e}"))?;
+
+    let keys = array.elements.iter().map(|e| Some(e.value.as_str()));
+    let at = insert_position(keys, member)?;
+    Ok(insert_element(
+        manifest,
+        &array,
+        at,
+        &format!("\"{member}\""),
+    ))
+}
+
+/// Adds a path dependency on the day project `name` to the `[dependencies]`
+/// table, on its own line among the other dependencies.
+fn add_dependency(manifest: &str, name: &str) -> Result<String, String> {
+    let items = parse_toml(manifest).map_err(|e| format!("invalid runner manifest: {e}"))?;
+    let header = items
+        .iter()
+        .position(|i| i.header && i.path == ["dependencies"])
+        .ok_or("no [dependencies] table found")?;
+    // Dependencies may also be given as `[dependencies.name]` tables.
+    if items
+        .iter()
+        .any(|i| i.path.len() > 1 && i.path[0] == "dependencies" && i.path[1] == name)
+    {
+        return Err(format!("{name} is already registered"));
+    }
+
+    let entries: Vec<&Item> = items[header + 1..]
+        .iter()
+        .take_while(|i| !i.header)
+        .collect();
+    let at = insert_position(entries.iter().map(|i| Some(i.path[1].as_str())), name)?;
+    let line = format!("{name} = {{ version = \"0.1.0\", path = \"../{name}\" }}");
+
+    let mut res = manifest.to_string();
+    if let Some(next) = entries.get(at) {
+        let indent = indentation(manifest, next.start).unwrap_or_default();
+        res.insert_str(next.start, &format!("{line}\n{indent}"));
+    } else {
+        let last = entries.last().copied().unwrap_or(&items[header]);
+        let indent = match entries.last() {
+            Some(last) => indentation(manifest, last.start).unwrap_or_default(),
+            None => "",
+        };
+        let sep = if manifest[..last.line_end].ends_with('\n') {
+            ""
+        } else {
+            "\n"
+        };
+        res.insert_str(last.line_end, &format!("{sep}{indent}{line}\n"));
+    }
+    Ok(res)
+}
+
+/// Registers the solution of `day` in the `DAYS` list of the runner, keeping
+/// the layout of the list.
+fn register_day(main: &str, day: u8) -> Result<String, String> {
+    let decl = main.find("const DAYS").ok_or("no DAYS list found")?;
+    let open = main[decl..]
+        .find('=')
+        .and_then(|i| main[decl + i..].find('[').map(|j| decl + i + j))
+        .ok_or("no DAYS list found")?;
+    let array = parse_list(
+        main,
+        open,
+        b']',
+        |pos| skip_rust_space(main, pos),
+        |pos| parse_days_entry(main, pos),
+    )
+    .map_err(|e| format!("invalid DAYS list: {e}"))?;
+
+    let keys = array.elements.iter().map(|e| Some(e.value.as_str()));
+    let at = insert_position(keys, &format!("day-{day:02}"))?;
+    let entry = format!("|| Day::new::<day_{day:02}::Day{day:02}>({day})");
+    Ok(insert_element(main, &array, at, &entry))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const WORKSPACE: &str = r#"[workspace]
+resolver = "2"
+members = [
+    "lib",
+    "aoc",
+    "day-01",
+    "day-03",
+]
+"#;
+
+    const RUNNER: &str = r#"[package]
+name = "aoc"
+
+[dependencies]
+lib = { version = "0.1.0", path = "../lib" }
+day-01 = { version = "0.1.0", path = "../day-01" }
+day-03 = { version = "0.1.0", path = "../day-03" }
+"#;
+
+    const MAIN: &str = "\
+const DAYS: &[fn() -> Day] = &[
+    || Day::new::<day_01::Day01>(1),
+    || Day::new::<day_03::Day03>(3),
+];
+";
+
+    #[test]
+    fn test_add_member() {
+        let res = add_member(WORKSPACE, "day-02").unwrap();
+        assert!(res.contains("    \"day-01\",\n    \"day-02\",\n    \"day-03\",\n]"));
+
+        let res = add_member(WORKSPACE, "day-10").unwrap();
+        assert!(res.contains("    \"day-03\",\n    \"day-10\",\n]"));
+
+        assert!(add_member(WORKSPACE, "day-03").is_err());
+        assert!(add_member("[package]\n", "day-03").is_err());
+    }
+
+    #[test]
+    fn test_add_member_array_shapes() {
+        let add = |members: &str| {
+            let manifest = format!("[workspace]\nmembers = {members}\n\n[profile.release]\n");
+            let res = add_member(&manifest, "day-02")?;
+            let members = res
+                .strip_prefix("[workspace]\nmembers = ")
+                .and_then(|r| r.strip_suffix("\n\n[profile.release]\n"))
+                .unwrap();
+            Ok::<_, String>(members.to_string())
+        };
+
+        assert_eq!(
+            add(r#"["lib", "aoc"]"#).unwrap(),
+            r#"["lib", "aoc", "day-02"]"#
+        );
+        assert_eq!(
+            add(r#"["lib", "day-01", "day-03",]"#).unwrap(),
+            r#"["lib", "day-01", "day-02", "day-03",]"#
+        );
+        assert_eq!(add("[]").unwrap(), r#"["day-02"]"#);
+        assert_eq!(add("[\n]").unwrap(), "[\n    \"day-02\",\n]");
+        assert_eq!(
+            add("[\n  'lib', # helpers\n  \"day-01\" # first\n]").unwrap(),
+            "[\n  'lib', # helpers\n  \"day-01\", # first\n  \"day-02\",\n]"
+        );
+        assert_eq!(
+            add("[ # days follow\n\t\"day-03\",\n\t# \"day-02\",\n]").unwrap(),
+            "[ # days follow\n\t\"day-02\",\n\t\"day-03\",\n\t# \"day-02\",\n]"
+        );
+        assert_eq!(
+            add("[\"lib\",\n \"day-01\"]").unwrap(),
+            "[\"lib\",\n \"day-01\", \"day-02\"]"
+        );
+
+        assert_eq!(
+            add("[\"day-02\"]").unwrap_err(),
+            "day-02 is already registered"
+        );
+        assert!(add("[\"lib\" \"aoc\"]").is_err());
+        assert!(add("[\"lib\",").is_err());
+        assert!(add("[\"lib]").is_err());
+        assert!(add("\"lib\"").is_err());
+    }
+
+    #[test]
+    fn test_add_member_without_days() {
+        let manifest = "[workspace]\nmembers = [\n    \"lib\",\n]\n";
+        let res = add_member(manifest, "day-01").unwrap();
+        assert_eq!(
+            res,
+            "[workspace]\nmembers = [\n    \"lib\",\n    \"day-01\",\n]\n"
+        );
+    }
+
+    #[test]
+    fn test_add_dependency() {
+        let res = add_dependency(RUNNER, "day-02").unwrap();
+        assert!(res.contains(
+            "day-01 = { version = \"0.1.0\", path = \"../day-01\" }\n\
+             day-02 = { version = \"0.1.0\", path = \"../day-02\" }\n"
+        ));
+        assert!(add_dependency(RUNNER, "day-01").is_err());
+    }
+
+    #[test]
+    fn test_add_dependency_table_shapes() {
+        let manifest = r#"[package]
+name = "aoc"
+
+[dependencies]
+lib = { path = "../lib", features = [
+    "grid", # for day 06
+] }
+day-01 = { version = "0.1.0", path = "../day-01" } # first
+"day-03".path = "../day-03"
+
+[dependencies.day-04]
+path = "../day-04"
+"#;
+        let line =
+            |name: &str| format!("{name} = {{ version = \"0.1.0\", path = \"../{name}\" }}\n");
+
+        let res = add_dependency(manifest, "day-02").unwrap();
+        assert_eq!(
+            res,
+            manifest.replace(
+                "\"day-03\".path",
+                &format!("{}\"day-03\".path", line("day-02"))
+            )
+        );
+        let res = add_dependency(manifest, "day-05").unwrap();
+        assert_eq!(
+            res,
+            manifest.replace("../day-03\"\n", &format!("../day-03\"\n{}", line("day-05")))
+        );
+        assert_eq!(
+            add_dependency(manifest, "day-04").unwrap_err(),
+            "day-04 is already registered"
+        );
+
+        let res = add_dependency("[dependencies]\n\n[dev-dependencies]\n", "day-01").unwrap();
+        assert_eq!(
+            res,
+            format!("[dependencies]\n{}\n[dev-dependencies]\n", line("day-01"))
+        );
+        let res = add_dependency("[dependencies]\nlib = \"0.1\"", "day-01").unwrap();
+        assert_eq!(
+            res,
+            format!("[dependencies]\nlib = \"0.1\"\n{}", line("day-01"))
+        );
+
+        assert!(add_dependency("[dependencies]\nlib = { path = \"../lib\"\n", "day-01").is_err());
+        assert!(add_dependency("[package]\n", "day-01").is_err());
+    }
+
+    #[test]
+    fn test_register_day() {
+        let res = register_day(MAIN, 2).unwrap();
+        assert_eq!(
+            res,
+            "\
+const DAYS: &[fn() -> Day] = &[
+    || Day::new::<day_01::Day01>(1),
+    || Day::new::<day_02::Day02>(2),
+    || Day::new::<day_03::Day03>(3),
+];
+"
+        );
+        assert!(register_day(MAIN, 3).is_err());
+    }
+
+    #[test]
+    fn test_register_day_list_shapes() {
+        let register = |days: &str, day| {
+            let main = format!("const DAYS: &[fn() -> Day] = &[{days}];\n\nfn main() {{}}\n");
+            let res = register_day(&main, day)?;
+            let days = res
+                .strip_prefix("const DAYS: &[fn() -> Day] = &[")
+                .and_then(|r| r.strip_suffix("];\n\nfn main() {}\n"))
+                .unwrap();
+            Ok::<_, String>(days.to_string())
+        };
+
+        assert_eq!(
+            register("|| Day::new::<day_01::Day01>(1)", 2).unwrap(),
+            "|| Day::new::<day_01::Day01>(1), || Day::new::<day_02::Day02>(2)"
+        );
+        assert_eq!(register("", 1).unwrap(), "|| Day::new::<day_01::Day01>(1)");
+
+        let days = "
+    // solved
+    || {
+        Day::new::<day_01::Day01>(1)
+    },
+    || Day::new::<day_03::Day03>(3) /* last */
+";
+        assert_eq!(
+            register(days, 2).unwrap(),
+            days.replace(
+                "    || Day::new::<day_03",
+                "    || Day::new::<day_02::Day02>(2),\n    || Day::new::<day_03"
+            )
+        );
+        assert_eq!(
+            register(days, 4).unwrap(),
+            days.replace(
+                "(3) /* last */\n",
+                "(3), /* last */\n    || Day::new::<day_04::Day04>(4),\n"
+            )
+        );
+        assert_eq!(
+            register(days, 1).unwrap_err(),
+            "day-01 is already registered"
+        );
+
+        assert!(register("|| Day::new::<day_01::Day01>(1))", 2).is_err());
+        assert!(register("(", 2).is_err());
+        assert!(register_day("fn main() {}\n", 2).is_err());
+    }
+
+    #[test]
+    fn test_scaffold() {
+        let root = std::env::temp_dir().join(format!("aoc-new-test-{}", std::process::id()));
+        fs::create_dir_all(root.join("aoc/src")).unwrap();
+        fs::write(root.join("Cargo.toml"), WORKSPACE).unwrap();
+        fs::write(root.join("aoc/Cargo.toml"), RUNNER).unwrap();
+        fs::write(root.join("aoc/src/main.rs"), MAIN).unwrap();
+
+        scaffold(&root, 2).unwrap();
+        assert!(root.join("day-02/src/lib.rs").is_file());
+        assert!(root.join("day-02/test_input.txt").is_file());
+        let lib = fs::read_to_string(root.join("day-02/src/lib.rs")).unwrap();
+        assert!(lib.contains("impl Solution for Day02"));
+
+        // Existing days are never overwritten.
+        fs::write(root.join("day-02/src/lib.rs"), "solved").unwrap();
+        assert!(scaffold(&root, 2).is_err());
+        assert_eq!(
+            fs::read_to_string(root.join("day-02/src/lib.rs")).unwrap(),
+            "solved"
+        );
+
+        // Registered days without a directory are not registered twice.
+        assert!(scaffold(&root, 3).is_err());
+        assert!(!root.join("day-03").exists());
+
+        assert!(scaffold(&root, 26).is_err());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,366 @@
+use crate::{
+    Args, Parsed, day_dir,
+    json::{self, Value},
+    print_header,
+};
+use core::fmt;
+use lib::{
+    Answers, Check, Day, InputSource, Part,
+    bench::{Short, measure},
+};
+use std::{
+    panic::{self, AssertUnwindSafe},
+    str::FromStr,
+    time::Duration,
+};
+
+/// Output format of the `run` command.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    /// Human readable output.
+    #[default]
+    Plain,
+    /// A single JSON array containing one object per answer.
+    Json,
+    /// One line per answer with a header line.
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "plain" => Ok(Self::Plain),
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            _ => Err(format!("invalid format '{s}', expected plain, json or csv")),
+        }
+    }
+}
+
+/// Answer of a single part.
+pub struct Record {
+    pub day: u8,
+    pub part: Part,
+    pub input: String,
+    pub answer: String,
+    /// Time it took to parse the input, if it was parsed for this part.
+    pub parse: Option<Duration>,
+    pub duration: Duration,
+    pub check: Check,
+}
+
+impl Record {
+    const CSV_HEADER: &str = "day,part,input,answer,parse_ns,duration_ns,status,expected";
+
+    fn to_json(&self) -> Value {
+        json::object([
+            ("day", self.day.into()),
+            ("part", self.part.number().into()),
+            ("input", self.input.as_str().into()),
+            ("answer", self.answer.as_str().into()),
+            ("parse_ns", self.parse.map(|d| d.as_nanos() as u64).into()),
+            ("duration_ns", (self.duration.as_nanos() as u64).into()),
+            ("status", self.check.status().into()),
+            ("expected", self.check.expected().into()),
+        ])
+    }
+
+    fn to_csv(&self) -> String {
+        [
+            self.day.to_string(),
+            self.part.to_string(),
+            csv_field(&self.input),
+            csv_field(&self.answer),
+            self.parse
+                .map(|d| d.as_nanos().to_string())
+                .unwrap_or_default(),
+            self.duration.as_nanos().to_string(),
+            self.check.status().to_owned(),
+            csv_field(self.check.expected().unwrap_or_default()),
+        ]
+        .join(",")
+    }
+
+    fn print_plain(&self) {
+        if let Some(parse) = self.parse {
+            println!("Parsed input in {}", Short(parse));
+        }
+        println!(
+            "{} ({})",
+            lib::format_answer(self.part, &self.answer, &self.check),
+            Short(self.duration)
+        );
+    }
+}
+
+/// Quotes a CSV field if necessary.
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
+
+pub fn run(args: &Args) -> Result<(), String> {
+    let days = args.days()?;
+    let parts = args.parts();
+
+    if args.format == Format::Csv {
+        println!("{}", Record::CSV_HEADER);
+    }
+
+    let mut records = vec![];
+    let mut single_err = None;
+    let summary = run_days(
+        &days,
+        &parts,
+        &args.source,
+        |i, day| {
+            if args.format == Format::Plain {
+                print_header(i, days.len(), day);
+            }
+        },
+        |record| match args.format {
+            Format::Plain => record.print_plain(),
+            Format::Csv => println!("{}", record.to_csv()),
+            Format::Json => records.push(record.to_json()),
+        },
+        |day, err| match days.len() {
+            1 => single_err = Some(err),
+            _ => eprintln!("error: day {}: {err}", day.day),
+        },
+    );
+    if let Some(err) = single_err {
+        return Err(err);
+    }
+
+    match args.format {
+        Format::Json => println!("{}", Value::Array(records)),
+        Format::Plain if args.verify => println!("\n{summary}"),
+        _ => {}
+    }
+
+    if summary.failed > 0 {
+        Err(format!("{} days failed to run", summary.failed))
+    } else if args.verify && summary.wrong > 0 {
+        Err(format!("{} answers do not match", summary.wrong))
+    } else if args.verify && summary.unverified > 0 {
+        Err(format!("{} answers are unverified", summary.unverified))
+    } else {
+        Ok(())
+    }
+}
+
+/// Runs the given parts of each of `days` and counts the results. `on_start`
+/// is called before each day, `on_record` with every answer and `on_error`
+/// for every day which failed to run or panicked.
+fn run_days(
+    days: &[Day],
+    parts: &[Part],
+    source: &InputSource,
+    mut on_start: impl FnMut(usize, &Day),
+    mut on_record: impl FnMut(Record),
+    mut on_error: impl FnMut(&Day, String),
+) -> Summary {
+    let mut summary = Summary::default();
+    for (i, day) in days.iter().enumerate() {
+        on_start(i, day);
+
+        // A failing day should not keep the remaining days from running,
+        // whether it returns an error or panics on a malformed input.
+        let res = panic::catch_unwind(AssertUnwindSafe(|| {
+            run_day(day, parts, source, |record| {
+                summary.add(&record.check);
+                on_record(record);
+            })
+        }));
+        let err = match res {
+            Ok(Ok(())) => continue,
+            Ok(Err(err)) => err,
+            Err(_) => "the solution panicked".into(),
+        };
+        summary.failed += 1;
+        on_error(day, err);
+    }
+    summary
+}
+
+/// Runs the given parts of `day` and passes the answer of each part to
+/// `on_record` as soon as it is available.
+pub fn run_day(
+    day: &Day,
+    parts: &[Part],
+    source: &InputSource,
+    mut on_record: impl FnMut(Record),
+) -> Result<(), String> {
+    let answers = Answers::load(day_dir(day.day).join("answers.toml"))?;
+    let key = source.answers_key();
+    let input = match source {
+        InputSource::Path(path) => path.display().to_string(),
+        InputSource::Stdin => "stdin".into(),
+        source => source.answers_key().unwrap_or_default().into(),
+    };
+
+    let mut parsed = None;
+    for &part in parts {
+        let (p, fresh) = Parsed::for_part(parsed.take(), day, source, part)?;
+
+        let (answer, duration) = measure(|| day.solve(p.input.as_ref(), part));
+        let check = match key {
+            Some(key) => answers.check(key, part, &answer),
+            None => Check::Unverified,
+        };
+
+        on_record(Record {
+            day: day.day,
+            part,
+            input: input.clone(),
+            answer,
+            parse: fresh.then_some(p.duration),
+            duration,
+            check,
+        });
+        parsed = Some(p);
+    }
+
+    Ok(())
+}
+
+/// Number of answers by their [`Check`] result over all run days.
+#[derive(Debug, Default)]
+struct Summary {
+    correct: usize,
+    wrong: usize,
+    unverified: usize,
+    failed: usize,
+}
+
+impl Summary {
+    fn add(&mut self, check: &Check) {
+        match check {
+            Check::Correct => self.correct += 1,
+            Check::Wrong { .. } => self.wrong += 1,
+            Check::Unverified => self.unverified += 1,
+        }
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} correct, {} wrong, {} unverified",
+            self.correct, self.wrong, self.unverified
+        )?;
+        if self.failed > 0 {
+            write!(f, ", {} days failed to run", self.failed)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use lib::Solution;
+    use std::fs;
+
+    struct Panics;
+
+    impl Solution for Panics {
+        type Input = ();
+
+        fn parse(_: &str) -> Self::Input {
+            panic!("malformed input")
+        }
+
+        fn part1(_: &Self::Input) -> impl fmt::Display {
+            1
+        }
+
+        fn part2(_: &Self::Input) -> impl fmt::Display {
+            2
+        }
+    }
+
+    struct Length;
+
+    impl Solution for Length {
+        type Input = usize;
+
+        fn parse(input: &str) -> Self::Input {
+            input.len()
+        }
+
+        fn part1(input: &Self::Input) -> impl fmt::Display {
+            *input
+        }
+
+        fn part2(input: &Self::Input) -> impl fmt::Display {
+            input * 2
+        }
+    }
+
+    #[test]
+    fn test_panicking_day_is_counted() {
+        let path = std::env::temp_dir().join(format!("aoc-run-test-{}", std::process::id()));
+        fs::write(&path, "abc").unwrap();
+
+        let days = [
+            Day::new::<Length>(97),
+            Day::new::<Panics>(98),
+            Day::new::<Length>(99),
+        ];
+        let mut answers = vec![];
+        let mut errors = vec![];
+        let summary = run_days(
+            &days,
+            &Part::all(),
+            &InputSource::Path(path.clone()),
+            |_, _| {},
+            |record| answers.push((record.day, record.answer)),
+            |day, err| errors.push((day.day, err)),
+        );
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            answers,
+            [
+                (97, "3".into()),
+                (97, "6".into()),
+                (99, "3".into()),
+                (99, "6".into())
+            ]
+        );
+        assert_eq!(errors, [(98, "the solution panicked".to_string())]);
+        assert_eq!(summary.failed, 1);
+        assert_eq!(summary.unverified, 4);
+    }
+
+    #[test]
+    fn test_record_formats() {
+        let record = Record {
+            day: 7,
+            part: Part::Two,
+            input: "my,input.txt".into(),
+            answer: "42".into(),
+            parse: None,
+            duration: Duration::from_micros(3),
+            check: Check::Wrong {
+                expected: "4\"2".into(),
+            },
+        };
+
+        assert_eq!(
+            record.to_csv(),
+            r#"7,2,"my,input.txt",42,,3000,wrong,"4""2""#
+        );
+        assert_eq!(
+            record.to_json().to_string(),
+            r#"{"day":7,"part":2,"input":"my,input.txt","answer":"42","parse_ns":null,"duration_ns":3000,"status":"wrong","expected":"4\"2"}"#
+        );
+    }
+}
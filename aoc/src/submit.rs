@@ -0,0 +1,296 @@
+use crate::{
+    DAYS, Parsed,
+    client::{Outcome, Reply},
+    day_dir, fetch,
+    json::{self, Value},
+    workspace_dir,
+};
+use lib::{Answers, Check, InputSource, Part};
+use std::{
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+/// Computes the answer of `part` of `day` for the puzzle input and submits
+/// it, unless the attempt log shows that it can not be correct.
+pub fn submit(day: u8, part: Part) -> Result<(), String> {
+    let solution = DAYS
+        .iter()
+        .map(|d| d())
+        .find(|d| d.day == day)
+        .ok_or_else(|| format!("day {day} has no solution"))?;
+
+    let (parsed, _) = Parsed::for_part(None, &solution, &InputSource::Puzzle, part)?;
+    let answer = solution.solve(parsed.input.as_ref(), part);
+
+    let answers = Answers::load(day_dir(day).join("answers.toml"))?;
+    let check = answers.check("input", part, &answer);
+    println!("{}", lib::format_answer(part, &answer, &check));
+    match check {
+        Check::Correct => {
+            println!("The answer is already known to be correct.");
+            return Ok(());
+        }
+        Check::Wrong { expected } => {
+            return Err(format!(
+                "the expected answer in answers.toml is {expected}, not submitting"
+            ));
+        }
+        Check::Unverified => {}
+    }
+
+    let client = fetch::client();
+    let mut attempts = Attempts::load(
+        &workspace_dir()
+            .join(".cache/attempts")
+            .join(format!("{}.json", client.year())),
+    )?;
+    attempts.check(day, part, &answer, now())?;
+
+    let reply = client
+        .submit(day, part, &answer)
+        .map_err(|e| e.to_string())?;
+    attempts.push(day, part, &answer, &reply, now());
+    attempts.save()?;
+
+    println!("{}", reply.message);
+    match reply.outcome {
+        Outcome::Correct => {
+            println!("Add `part{part} = {answer}` to the [input] table of answers.toml.");
+            Ok(())
+        }
+        outcome => Err(format!("the answer has not been accepted: {outcome}")),
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// A submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Attempt {
+    day: u8,
+    part: Part,
+    answer: String,
+    outcome: Outcome,
+    /// Unix timestamp of the submission.
+    timestamp: u64,
+    /// Unix timestamp after which the next answer can be submitted.
+    retry_at: Option<u64>,
+}
+
+impl Attempt {
+    fn to_json(&self) -> Value {
+        json::object([
+            ("day", self.day.into()),
+            ("part", self.part.number().into()),
+            ("answer", self.answer.as_str().into()),
+            ("outcome", self.outcome.key().into()),
+            ("timestamp", self.timestamp.into()),
+            ("retry_at", self.retry_at.into()),
+        ])
+    }
+
+    fn from_json(value: &Value) -> Option<Self> {
+        Some(Self {
+            day: value.get("day")?.as_f64()? as u8,
+            part: (value.get("part")?.as_f64()? as u8)
+                .to_string()
+                .parse()
+                .ok()?,
+            answer: value.get("answer")?.as_str()?.to_owned(),
+            outcome: Outcome::from_key(value.get("outcome")?.as_str()?)?,
+            timestamp: value.get("timestamp")?.as_f64()? as u64,
+            retry_at: value
+                .get("retry_at")
+                .and_then(Value::as_f64)
+                .map(|t| t as u64),
+        })
+    }
+}
+
+/// Log of all submitted answers of a year, stored as a JSON array with one
+/// attempt per line.
+struct Attempts {
+    path: PathBuf,
+    entries: Vec<Attempt>,
+}
+
+impl Attempts {
+    /// Loads the log from `path`. A missing file results in an empty log.
+    fn load(path: &Path) -> Result<Self, String> {
+        let entries = match std::fs::read_to_string(path) {
+            Ok(s) => {
+                let root: Value = s.parse().map_err(|e| format!("{}: {e}", path.display()))?;
+                root.as_array()
+                    .ok_or_else(|| format!("{}: expected an array", path.display()))?
+                    .iter()
+                    .map(|v| {
+                        Attempt::from_json(v)
+                            .ok_or_else(|| format!("{}: invalid attempt {v}", path.display()))
+                    })
+                    .collect::<Result<_, _>>()?
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => vec![],
+            Err(e) => return Err(format!("failed reading {}: {e}", path.display())),
+        };
+
+        Ok(Self {
+            path: path.to_owned(),
+            entries,
+        })
+    }
+
+    /// Returns an error if submitting `answer` at `now` is pointless: the
+    /// part has already been solved, the answer has already been rejected
+    /// or is out of the bounds given by earlier answers, or the cooldown of
+    /// the last submission has not passed yet.
+    fn check(&self, day: u8, part: Part, answer: &str, now: u64) -> Result<(), String> {
+        let attempts: Vec<_> = self
+            .entries
+            .iter()
+            .filter(|a| a.day == day && a.part == part)
+            .collect();
+
+        if let Some(correct) = attempts.iter().find(|a| a.outcome == Outcome::Correct) {
+            return Err(format!(
+                "part {part} of day {day} has already been solved with {}",
+                correct.answer
+            ));
+        }
+
+        if let Some(rejected) = attempts
+            .iter()
+            .find(|a| a.answer == answer && a.outcome.is_rejected())
+        {
+            return Err(format!(
+                "{answer} has already been rejected as {}",
+                rejected.outcome
+            ));
+        }
+
+        if let Ok(n) = answer.parse::<i128>() {
+            for a in &attempts {
+                let Ok(bound) = a.answer.parse::<i128>() else {
+                    continue;
+                };
+                match a.outcome {
+                    Outcome::TooHigh if n >= bound => {
+                        return Err(format!("{answer} is too high, {bound} already was"));
+                    }
+                    Outcome::TooLow if n <= bound => {
+                        return Err(format!("{answer} is too low, {bound} already was"));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if let Some(retry_at) = attempts.iter().filter_map(|a| a.retry_at).max()
+            && retry_at > now
+        {
+            return Err(format!(
+                "the last answer has been submitted too recently, wait {}s",
+                retry_at - now
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Records the reply to a submitted answer.
+    fn push(&mut self, day: u8, part: Part, answer: &str, reply: &Reply, now: u64) {
+        self.entries.push(Attempt {
+            day,
+            part,
+            answer: answer.to_owned(),
+            outcome: reply.outcome,
+            timestamp: now,
+            retry_at: reply.wait.map(|w| now + w.as_secs()),
+        });
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let lines: Vec<_> = self
+            .entries
+            .iter()
+            .map(|a| format!("  {}", a.to_json()))
+            .collect();
+        let out = format!("[\n{}\n]\n", lines.join(",\n"));
+
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| format!("failed creating {}: {e}", dir.display()))?;
+        }
+        std::fs::write(&self.path, out)
+            .map_err(|e| format!("failed writing {}: {e}", self.path.display()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    fn reply(outcome: Outcome, wait: Option<u64>) -> Reply {
+        Reply {
+            outcome,
+            wait: wait.map(Duration::from_secs),
+            message: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_check_attempts() {
+        let path = std::env::temp_dir().join(format!("aoc-attempts-{}.json", std::process::id()));
+        let mut attempts = Attempts::load(&path).unwrap();
+        assert!(attempts.entries.is_empty());
+
+        attempts.push(
+            1,
+            Part::One,
+            "100",
+            &reply(Outcome::TooHigh, Some(60)),
+            1000,
+        );
+        attempts.push(1, Part::One, "10", &reply(Outcome::TooLow, Some(60)), 1100);
+        attempts.push(1, Part::One, "abc", &reply(Outcome::Wrong, Some(300)), 1200);
+        attempts.push(
+            1,
+            Part::One,
+            "50",
+            &reply(Outcome::RateLimited, Some(30)),
+            1210,
+        );
+
+        // Answers are checked against the bounds of earlier answers.
+        assert!(attempts.check(1, Part::One, "100", 2000).is_err());
+        assert!(attempts.check(1, Part::One, "150", 2000).is_err());
+        assert!(attempts.check(1, Part::One, "10", 2000).is_err());
+        assert!(attempts.check(1, Part::One, "-3", 2000).is_err());
+        assert!(attempts.check(1, Part::One, "abc", 2000).is_err());
+        assert!(attempts.check(1, Part::One, "11", 2000).is_ok());
+        assert!(attempts.check(1, Part::One, "xyz", 2000).is_ok());
+
+        // Rate limited answers have not been checked.
+        assert!(attempts.check(1, Part::One, "50", 2000).is_ok());
+
+        // The cooldown is tracked per part.
+        assert!(attempts.check(1, Part::One, "50", 1499).is_err());
+        assert!(attempts.check(1, Part::Two, "100", 1499).is_ok());
+
+        attempts.push(1, Part::One, "42", &reply(Outcome::Correct, None), 2000);
+        assert!(attempts.check(1, Part::One, "42", 3000).is_err());
+
+        // The log is restored as it has been saved.
+        attempts.save().unwrap();
+        let loaded = Attempts::load(&path).unwrap();
+        assert_eq!(loaded.entries, attempts.entries);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,30 @@
+use lib::*;
+use std::fmt::Display;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = (Vec<isize>, Vec<isize>);
+
+    fn parse(input: &str) -> Self::Input {
+        let [mut left, mut right] = parse::columns(input).expect("two columns of integers");
+
+        left.sort();
+        right.sort();
+
+        (left, right)
+    }
+
+    fn part1((left, right): &Self::Input) -> impl Display {
+        left.iter()
+            .zip(right.iter())
+            .map(|(l, r)| (l - r).abs())
+            .sum::<isize>()
+    }
+
+    fn part2((left, right): &Self::Input) -> impl Display {
+        left.iter()
+            .map(|l| right.iter().filter(|&r| r == l).count() as isize * l)
+            .sum::<isize>()
+    }
+}
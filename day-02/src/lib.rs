@@ -1,24 +1,24 @@
 #![feature(iter_map_windows)]
 
 use lib::*;
+use std::fmt::Display;
 
-fn main() {
-    let input: String = lib::read_input!();
+pub struct Day02;
 
-    let records: Vec<Vec<isize>> = input
-        .lines()
-        .map(|l| {
-            l.split_ascii_whitespace()
-                .map(|c| c.parse().unwrap())
-                .collect()
-        })
-        .collect();
+impl Solution for Day02 {
+    type Input = Vec<Vec<isize>>;
 
-    let safe_records = records.iter().filter(|v| is_safe_record(v, false)).count();
-    p1!(safe_records);
+    fn parse(input: &str) -> Self::Input {
+        parse::int_lines(input).expect("integer records")
+    }
+
+    fn part1(records: &Self::Input) -> impl Display {
+        records.iter().filter(|v| is_safe_record(v, false)).count()
+    }
 
-    let safe_records = records.iter().filter(|v| is_safe_record(v, true)).count();
-    p2!(safe_records);
+    fn part2(records: &Self::Input) -> impl Display {
+        records.iter().filter(|v| is_safe_record(v, true)).count()
+    }
 }
 
 fn is_safe_record(record: &[isize], tolerance: bool) -> bool {
@@ -0,0 +1,60 @@
+use lib::{
+    vm::{ArgType, Token, Vm},
+    *,
+};
+use std::fmt::Display;
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Vec<Token>;
+
+    fn parse(input: &str) -> Self::Input {
+        vm().tokenize(input)
+    }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        let mut sum = 0;
+        vm().ignore_toggles().execute(input, &mut sum);
+        sum
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        let mut sum = 0;
+        vm().execute(input, &mut sum);
+        sum
+    }
+}
+
+/// Arguments of `mul` are numbers of one to three digits.
+const ARG: ArgType = ArgType::UInt { max_digits: 3 };
+
+fn vm() -> Vm<i64> {
+    Vm::new()
+        .instruction("mul", &[ARG, ARG], |sum, args| {
+            *sum += args[0].int() * args[1].int()
+        })
+        .enable("do")
+        .disable("don't")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn solve() {
+        let input = Day03::parse(
+            "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))",
+        );
+        assert_eq!(Day03::part1(&input).to_string(), "161");
+        assert_eq!(Day03::part2(&input).to_string(), "48");
+    }
+
+    #[test]
+    fn rejects_signed_and_long_numbers() {
+        let input = Day03::parse("mul(-5,6)mul(1234,5)mul(5,1234)mul(2,3)");
+        assert_eq!(input.len(), 1);
+        assert_eq!(Day03::part1(&input).to_string(), "6");
+    }
+}
@@ -0,0 +1,61 @@
+use lib::*;
+use std::fmt::Display;
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Grid<char>;
+
+    fn parse(input: &str) -> Self::Input {
+        input.parse().expect("grid")
+    }
+
+    fn part1(grid: &Self::Input) -> impl Display {
+        count_xmas(grid)
+    }
+
+    fn part2(grid: &Self::Input) -> impl Display {
+        count_x_mas(grid)
+    }
+}
+
+fn count_xmas(grid: &Grid<char>) -> usize {
+    let mut count = 0;
+
+    for x_pos in grid.find_all(&'X') {
+        for m in grid.all_neighbours(x_pos).equal_to(&'M') {
+            let Some('A') = grid.get(x_pos + m.dir * 2isize) else {
+                continue;
+            };
+
+            let Some('S') = grid.get(x_pos + m.dir * 3isize) else {
+                continue;
+            };
+
+            count += 1;
+        }
+    }
+
+    count
+}
+
+fn count_x_mas(grid: &Grid<char>) -> usize {
+    let mut count = 0;
+
+    'outer: for a_pos in grid.find_all(&'A') {
+        let ms: Vec<_> = grid.diagonal_neighbours(a_pos).equal_to(&'M').collect();
+        if ms.len() != 2 {
+            continue;
+        }
+
+        for m in ms {
+            let Some('S') = grid.get(a_pos + m.dir.reverse().into()) else {
+                continue 'outer;
+            };
+        }
+
+        count += 1;
+    }
+
+    count
+}
@@ -0,0 +1,74 @@
+use lib::{graph::Rules, record::AocParse, *};
+use std::fmt::Display;
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = (Rules<usize>, Vec<Vec<usize>>);
+
+    fn parse(input: &str) -> Self::Input {
+        let (rules, orders): (Vec<Rule>, _) =
+            parse::typed_sections(input).expect("rules and orders");
+        let rules = rules.into_iter().map(|Rule(l, r)| (l, r)).collect();
+        (rules, orders)
+    }
+
+    fn part1((rules, orders): &Self::Input) -> impl Display {
+        orders
+            .iter()
+            .filter(|order| rules.is_ordered(order))
+            .map(|order| get_pivot_value(order))
+            .sum::<usize>()
+    }
+
+    fn part2((rules, orders): &Self::Input) -> impl Display {
+        orders
+            .iter()
+            .filter(|order| !rules.is_ordered(order))
+            .map(|order| rules.toposort(order).expect("rules without cycles"))
+            .map(|order| get_pivot_value(&order))
+            .sum::<usize>()
+    }
+}
+
+/// Rule that the left page has to be printed before the right one.
+#[derive(AocParse)]
+#[aoc(pattern = "{0}|{1}")]
+struct Rule(usize, usize);
+
+fn get_pivot_value<T: Copy>(s: &[T]) -> T {
+    s[s.len() / 2]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reorder() {
+        let (rules, orders) = Day05::parse(include_str!("../test_input.txt"));
+
+        let ordered: Vec<_> = orders.iter().map(|o| rules.is_ordered(o)).collect();
+        assert_eq!(ordered, vec![true, true, true, false, false, false]);
+
+        assert_eq!(rules.toposort(&orders[3]), Ok(vec![97, 75, 47, 61, 53]));
+        assert_eq!(rules.toposort(&orders[4]), Ok(vec![61, 29, 13]));
+        assert_eq!(rules.toposort(&orders[5]), Ok(vec![97, 75, 47, 29, 13]));
+    }
+
+    #[test]
+    fn reorder_with_rule_cycle() {
+        // The rules as a whole contain the cycle 1 -> 2 -> 3 -> 1, which only
+        // matters for orders containing all three pages.
+        let (rules, _) = Day05::parse("1|2\n2|3\n3|1\n3|4\n\n1");
+
+        assert!(rules.is_ordered(&[1, 2, 4]));
+        assert_eq!(rules.toposort(&[4, 3, 2]), Ok(vec![2, 3, 4]));
+
+        let err = rules.toposort(&[4, 3, 2, 1]).unwrap_err();
+        let mut cycle = err.cycle;
+        cycle.sort();
+        assert_eq!(cycle, vec![1, 2, 3]);
+        assert!(!rules.is_ordered(&[1, 2, 3]));
+    }
+}
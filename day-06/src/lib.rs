@@ -0,0 +1,170 @@
+use lib::{cycle::GridWalker, *};
+use std::fmt::Display;
+
+type State = (Pos, Direction);
+
+pub struct Day06;
+
+pub struct Input {
+    grid: Grid<char>,
+    start: State,
+}
+
+impl Solution for Day06 {
+    type Input = Input;
+
+    fn parse(input: &str) -> Self::Input {
+        let grid: Grid<char> = input.parse().expect("grid");
+
+        // Both in the test input as well as in the real input, the start dierction is "up".
+        let start_pos = grid.find(&'^').expect("start position");
+
+        Input {
+            grid,
+            start: (start_pos, Direction::Up),
+        }
+    }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        let (visited, _) = walk(&input.grid, input.start);
+        visited.iter().filter(|(_, v)| **v).count()
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        // An obstruction can only change the guard's path if it is placed somewhere
+        // the guard actually walks. Up to the first time the guard reaches that
+        // position, the path stays the same, so the simulation can start right
+        // in front of the obstruction.
+        let (_, candidates) = walk(&input.grid, input.start);
+        let walker = GridWalker::new(&input.grid, |c| *c == '#');
+        let mut seen = Seen::new(&walker);
+        candidates
+            .iter()
+            .filter(|(state, obstruction)| seen.is_loop(&walker, *state, *obstruction))
+            .count()
+    }
+}
+
+/// Moves the guard one step forward or turns it clockwise, if the way is
+/// blocked by a wall. Returns `None` if the guard leaves the grid.
+fn step(grid: &Grid<char>, (pos, dir): State) -> Option<State> {
+    let next = pos.mv(dir);
+    match grid.get(next)? {
+        '#' => Some((pos, dir.turn_cw())),
+        _ => Some((next, dir)),
+    }
+}
+
+/// Walks the guard from `start` until it leaves the grid and returns a map of
+/// all visited positions together with every newly visited position and
+/// the state right before the guard first entered it.
+fn walk(grid: &Grid<char>, start: State) -> (Grid<bool>, Vec<(State, Pos)>) {
+    let mut visited = Grid::filled(grid.width(), grid.height(), false);
+    let mut candidates = vec![];
+
+    visited[start.0] = true;
+
+    let mut state = start;
+    while let Some(next) = step(grid, state) {
+        if !visited[next.0] {
+            visited[next.0] = true;
+            candidates.push((state, next.0));
+        }
+        state = next;
+    }
+
+    (visited, candidates)
+}
+
+/// States the guard has been in, shared between the checked obstructions.
+/// Instead of clearing the buffer before each check, states are marked with
+/// the number of the check.
+struct Seen {
+    marks: Vec<u32>,
+    check: u32,
+}
+
+impl Seen {
+    fn new(walker: &GridWalker) -> Self {
+        Self {
+            marks: vec![0; walker.states()],
+            check: 0,
+        }
+    }
+
+    /// Returns true if the guard starting at `start` never leaves the grid
+    /// when an obstruction is placed at `obstruction`.
+    fn is_loop(&mut self, walker: &GridWalker, start: State, obstruction: Pos) -> bool {
+        self.check += 1;
+        let mut state = start;
+
+        loop {
+            let idx = walker.state_index(state).expect("guard inside the grid");
+            if self.marks[idx] == self.check {
+                return true;
+            }
+            self.marks[idx] = self.check;
+
+            match walker.step(state, Some(obstruction)) {
+                Some(next) => state = next,
+                None => return false,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn test_grid() -> (Grid<char>, State) {
+        let grid: Grid<char> = include_str!("../test_input.txt").parse().unwrap();
+        let start = grid.find(&'^').unwrap();
+        (grid, (start, Direction::Up))
+    }
+
+    #[test]
+    fn visited_positions() {
+        let (grid, start) = test_grid();
+        let (visited, candidates) = walk(&grid, start);
+        assert_eq!(visited.iter().filter(|(_, v)| **v).count(), 41);
+        assert_eq!(candidates.len(), 40);
+    }
+
+    #[test]
+    fn loop_obstructions() {
+        let (grid, start) = test_grid();
+        let (_, candidates) = walk(&grid, start);
+        let walker = GridWalker::new(&grid, |c| *c == '#');
+        let mut seen = Seen::new(&walker);
+
+        let mut loops: Vec<_> = candidates
+            .iter()
+            .filter(|(state, o)| seen.is_loop(&walker, *state, *o))
+            .map(|(_, o)| *o)
+            .collect();
+        loops.sort_by_key(|p| (p.y, p.x));
+
+        assert_eq!(
+            loops,
+            vec![
+                Pos { x: 3, y: 6 },
+                Pos { x: 6, y: 7 },
+                Pos { x: 7, y: 7 },
+                Pos { x: 1, y: 8 },
+                Pos { x: 3, y: 8 },
+                Pos { x: 7, y: 9 },
+            ]
+        );
+    }
+
+    #[test]
+    fn no_loop_from_start() {
+        let (grid, start) = test_grid();
+        let walker = GridWalker::new(&grid, |c| *c == '#');
+        let mut seen = Seen::new(&walker);
+        assert!(!seen.is_loop(&walker, start, Pos { x: 0, y: 0 }));
+        assert!(seen.is_loop(&walker, start, Pos { x: 3, y: 6 }));
+        assert!(!seen.is_loop(&walker, start, Pos { x: 0, y: 0 }));
+    }
+}
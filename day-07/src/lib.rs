@@ -0,0 +1,77 @@
+use lib::{
+    expr::{Add, Concat, Mul, Op},
+    record::{self, AocParse},
+    *,
+};
+use std::fmt::Display;
+
+#[derive(AocParse)]
+#[aoc(pattern = "{result}: {components: ' '}")]
+pub struct Entry {
+    result: usize,
+    components: Vec<usize>,
+}
+
+impl Entry {
+    fn solve<'a>(&self, ops: &[&'a dyn Op]) -> Option<Vec<&'a dyn Op>> {
+        expr::solve(self.result, &self.components, ops)
+    }
+}
+
+fn solve(entries: &[Entry], ops: &[&dyn Op]) -> usize {
+    entries
+        .iter()
+        .filter(|e| e.solve(ops).is_some())
+        .map(|e| e.result)
+        .sum()
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = Vec<Entry>;
+
+    fn parse(input: &str) -> Self::Input {
+        record::records(input).expect("entry parsing")
+    }
+
+    fn part1(entries: &Self::Input) -> impl Display {
+        solve(entries, &[&Add, &Mul])
+    }
+
+    fn part2(entries: &Self::Input) -> impl Display {
+        solve(entries, &[&Add, &Mul, &Concat])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn solutions() {
+        let entries: Vec<Entry> = record::records(include_str!("../test_input.txt")).unwrap();
+
+        let solutions: Vec<_> = entries
+            .iter()
+            .filter_map(|e| {
+                let ops = e.solve(&[&Add, &Mul, &Concat])?;
+                let solution = expr::Equation::new(e.result, &e.components, &ops);
+                assert!(solution.verify());
+                Some(solution.to_string())
+            })
+            .collect();
+
+        assert_eq!(
+            solutions,
+            vec![
+                "190 = 10 * 19",
+                "3267 = 81 * 40 + 27",
+                "156 = 15 || 6",
+                "7290 = 6 * 8 || 6 * 15",
+                "192 = 17 || 8 + 14",
+                "292 = 11 + 6 * 16 + 20",
+            ]
+        );
+    }
+}
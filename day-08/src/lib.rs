@@ -0,0 +1,99 @@
+use lib::*;
+use std::{collections::HashSet, fmt::Display};
+
+#[derive(PartialEq, Eq, Hash, Debug)]
+pub struct Antenna {
+    pos: Pos,
+    id: char,
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = (Grid<char>, Vec<Antenna>);
+
+    fn parse(input: &str) -> Self::Input {
+        let grid: Grid<char> = input.parse().expect("grid");
+
+        let antennae: Vec<_> = grid
+            .iter()
+            .filter(|(_, c)| **c != '.')
+            .map(|(pos, &id)| Antenna { pos, id })
+            .collect();
+
+        (grid, antennae)
+    }
+
+    fn part1((grid, antennae): &Self::Input) -> impl Display {
+        solve(grid, antennae, calculate_antinodes)
+    }
+
+    fn part2((grid, antennae): &Self::Input) -> impl Display {
+        solve(grid, antennae, calculate_antinodes_multi)
+    }
+}
+
+fn solve(
+    grid: &Grid<char>,
+    antennae: &[Antenna],
+    calculator: impl Fn(&Grid<char>, Pos, Pos) -> Vec<Pos>,
+) -> usize {
+    let mut calculated = HashSet::new();
+    let mut antinodes = HashSet::new();
+
+    for antenna in antennae {
+        if !calculated.insert(antenna) {
+            continue;
+        }
+
+        for op in antennae {
+            if op.pos == antenna.pos || op.id != antenna.id {
+                continue;
+            }
+
+            for a in calculator(grid, antenna.pos, op.pos) {
+                antinodes.insert(a);
+            }
+        }
+    }
+
+    antinodes.len()
+}
+
+fn calculate_antinodes(grid: &Grid<char>, a: Pos, b: Pos) -> Vec<Pos> {
+    let mut res = Vec::with_capacity(2);
+
+    let v = b - a;
+
+    let ap: Pos = a - v;
+    if grid.is_in_bounds(ap) {
+        res.push(ap);
+    }
+
+    let bp = b + v;
+    if grid.is_in_bounds(bp) {
+        res.push(bp);
+    }
+
+    res
+}
+
+fn calculate_antinodes_multi(grid: &Grid<char>, a: Pos, b: Pos) -> Vec<Pos> {
+    let mut res = Vec::with_capacity(2);
+
+    let v = b - a;
+
+    let mut lastp = a - v;
+    while grid.is_in_bounds(lastp) {
+        res.push(lastp);
+        lastp -= v;
+    }
+
+    let mut lastp = a;
+    while grid.is_in_bounds(lastp) {
+        res.push(lastp);
+        lastp += v;
+    }
+
+    res
+}
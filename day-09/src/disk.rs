@@ -0,0 +1,243 @@
+use core::fmt;
+use std::{cmp::Reverse, collections::BinaryHeap, str::FromStr};
+
+/// A contiguous range of blocks on the disk.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Extent {
+    start: usize,
+    len: usize,
+}
+
+/// Strategy used to compact the disk.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    /// Moves single blocks from the end of the disk into the leftmost free
+    /// block, which might split up files.
+    Fragment,
+    /// Moves whole files, starting with the highest file id, into the
+    /// leftmost free span which is large enough to hold the file.
+    WholeFiles,
+}
+
+/// Layout of files and free space on a disk.
+#[derive(Clone, Debug)]
+pub struct DiskMap {
+    /// Extents of every file indexed by file id. The first extent starts
+    /// out as the location given by the disk map. [`Mode::Fragment`]
+    /// shrinks it by the blocks moved into further extents, while
+    /// [`Mode::WholeFiles`] moves the file by changing its start.
+    files: Vec<Vec<Extent>>,
+    /// Free spans as described by the disk map, ordered by position.
+    free: Vec<Extent>,
+    size: usize,
+}
+
+impl FromStr for DiskMap {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut files = vec![];
+        let mut free = vec![];
+        let mut size = 0;
+
+        for (i, c) in s.trim().chars().enumerate() {
+            let len = c
+                .to_digit(10)
+                .ok_or_else(|| format!("invalid digit '{c}' at position {i}"))?
+                as usize;
+
+            let extent = Extent { start: size, len };
+            if i % 2 == 0 {
+                files.push(vec![extent]);
+            } else {
+                free.push(extent);
+            }
+            size += len;
+        }
+
+        Ok(Self { files, free, size })
+    }
+}
+
+impl DiskMap {
+    /// Compacts the disk using the given strategy.
+    pub fn compact(&mut self, mode: Mode) {
+        self.compact_with(mode, |_| {});
+    }
+
+    /// Compacts the disk using the given strategy and calls `on_move` after
+    /// every moved block in [`Mode::Fragment`] or every moved file in
+    /// [`Mode::WholeFiles`].
+    pub fn compact_with(&mut self, mode: Mode, on_move: impl FnMut(&DiskMap)) {
+        match mode {
+            Mode::Fragment => self.fragment(on_move),
+            Mode::WholeFiles => self.move_files(on_move),
+        }
+    }
+
+    fn fragment(&mut self, mut on_move: impl FnMut(&DiskMap)) {
+        let mut spans = std::mem::take(&mut self.free).into_iter();
+        let mut span = Extent { start: 0, len: 0 };
+
+        for id in (0..self.files.len()).rev() {
+            while self.files[id][0].len > 0 {
+                while span.len == 0 {
+                    let Some(next) = spans.next() else {
+                        return;
+                    };
+                    span = next;
+                }
+
+                let original = &mut self.files[id][0];
+                if span.start >= original.start + original.len {
+                    return;
+                }
+                original.len -= 1;
+
+                match self.files[id].last_mut() {
+                    Some(last) if last.start + last.len == span.start => last.len += 1,
+                    _ => self.files[id].push(Extent {
+                        start: span.start,
+                        len: 1,
+                    }),
+                }
+                span.start += 1;
+                span.len -= 1;
+
+                on_move(self);
+            }
+        }
+    }
+
+    fn move_files(&mut self, mut on_move: impl FnMut(&DiskMap)) {
+        // For every span length, a min-heap of the start positions of all
+        // free spans with exactly that length.
+        let max_len = self.free.iter().map(|s| s.len).max().unwrap_or_default();
+        let mut heaps = vec![BinaryHeap::new(); max_len + 1];
+        for span in std::mem::take(&mut self.free) {
+            if span.len > 0 {
+                heaps[span.len].push(Reverse(span.start));
+            }
+        }
+
+        for id in (0..self.files.len()).rev() {
+            let file = self.files[id][0];
+            if file.len == 0 || file.len > max_len {
+                continue;
+            }
+
+            let Some((len, start)) = (file.len..=max_len)
+                .filter_map(|len| heaps[len].peek().map(|Reverse(start)| (len, *start)))
+                .filter(|(_, start)| *start < file.start)
+                .min_by_key(|(_, start)| *start)
+            else {
+                continue;
+            };
+
+            heaps[len].pop();
+            if len > file.len {
+                heaps[len - file.len].push(Reverse(start + file.len));
+            }
+
+            self.files[id][0].start = start;
+
+            on_move(self);
+        }
+    }
+
+    /// Returns the file id of every block on the disk or `None` for free
+    /// blocks.
+    pub fn blocks(&self) -> Vec<Option<usize>> {
+        let mut blocks = vec![None; self.size];
+        for (id, extents) in self.files.iter().enumerate() {
+            for e in extents {
+                blocks[e.start..e.start + e.len].fill(Some(id));
+            }
+        }
+        blocks
+    }
+
+    /// Returns the sum of the position of every block multiplied by the id
+    /// of the file it belongs to.
+    pub fn checksum(&self) -> usize {
+        self.files
+            .iter()
+            .enumerate()
+            .flat_map(|(id, extents)| extents.iter().map(move |e| (id, e)))
+            .map(|(id, e)| id * (e.start * e.len + e.len * e.len.saturating_sub(1) / 2))
+            .sum()
+    }
+}
+
+impl fmt::Display for DiskMap {
+    /// Renders the block layout like `00...111...2`, where free blocks are
+    /// shown as `.` and file blocks as the last digit of their file id.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for block in self.blocks() {
+            match block {
+                Some(id) => write!(f, "{}", id % 10)?,
+                None => write!(f, ".")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = "2333133121414131402";
+
+    fn states(mode: Mode) -> (Vec<String>, usize) {
+        let mut disk: DiskMap = INPUT.parse().unwrap();
+        let mut states = vec![disk.to_string()];
+        disk.compact_with(mode, |d| states.push(d.to_string()));
+        (states, disk.checksum())
+    }
+
+    #[test]
+    fn fragment() {
+        let (states, checksum) = states(Mode::Fragment);
+        assert_eq!(
+            states,
+            vec![
+                "00...111...2...333.44.5555.6666.777.888899",
+                "009..111...2...333.44.5555.6666.777.88889.",
+                "0099.111...2...333.44.5555.6666.777.8888..",
+                "00998111...2...333.44.5555.6666.777.888...",
+                "009981118..2...333.44.5555.6666.777.88....",
+                "0099811188.2...333.44.5555.6666.777.8.....",
+                "009981118882...333.44.5555.6666.777.......",
+                "0099811188827..333.44.5555.6666.77........",
+                "00998111888277.333.44.5555.6666.7.........",
+                "009981118882777333.44.5555.6666...........",
+                "009981118882777333644.5555.666............",
+                "00998111888277733364465555.66.............",
+                "0099811188827773336446555566..............",
+            ]
+        );
+        assert_eq!(checksum, 1928);
+    }
+
+    #[test]
+    fn whole_files() {
+        let (states, checksum) = states(Mode::WholeFiles);
+        assert_eq!(
+            states,
+            vec![
+                "00...111...2...333.44.5555.6666.777.888899",
+                "0099.111...2...333.44.5555.6666.777.8888..",
+                "0099.1117772...333.44.5555.6666.....8888..",
+                "0099.111777244.333....5555.6666.....8888..",
+                "00992111777.44.333....5555.6666.....8888..",
+            ]
+        );
+        assert_eq!(checksum, 2858);
+    }
+
+    #[test]
+    fn invalid_digit() {
+        assert!("12a3".parse::<DiskMap>().is_err());
+    }
+}
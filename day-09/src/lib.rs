@@ -0,0 +1,27 @@
+pub mod disk;
+
+use disk::{DiskMap, Mode};
+use lib::*;
+use std::fmt::Display;
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = DiskMap;
+
+    fn parse(input: &str) -> Self::Input {
+        input.parse().expect("disk map")
+    }
+
+    fn part1(disk: &Self::Input) -> impl Display {
+        let mut fragmented = disk.clone();
+        fragmented.compact(Mode::Fragment);
+        fragmented.checksum()
+    }
+
+    fn part2(disk: &Self::Input) -> impl Display {
+        let mut compacted = disk.clone();
+        compacted.compact(Mode::WholeFiles);
+        compacted.checksum()
+    }
+}
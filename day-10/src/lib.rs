@@ -0,0 +1,43 @@
+use lib::{search::SearchResult, *};
+use std::fmt::Display;
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Grid<usize>;
+
+    fn parse(input: &str) -> Self::Input {
+        let grid: Grid<char> = input.parse().expect("grid");
+        grid.map(|&c| c as usize - 48)
+    }
+
+    fn part1(grid: &Self::Input) -> impl Display {
+        let ends = grid.find_all(&9);
+        trails(grid)
+            .map(|t| ends.iter().filter(|e| t.is_reached(e)).count())
+            .sum::<usize>()
+    }
+
+    fn part2(grid: &Self::Input) -> impl Display {
+        let ends = grid.find_all(&9);
+        trails(grid)
+            .map(|t| ends.iter().map(|e| t.path_count(e)).sum::<usize>())
+            .sum::<usize>()
+    }
+}
+
+/// Returns the search results of the trails from every trailhead.
+fn trails(grid: &Grid<usize>) -> impl Iterator<Item = SearchResult<Pos>> + '_ {
+    grid.find_all(&0)
+        .into_iter()
+        .map(|start| find_trails(grid, start))
+}
+
+fn find_trails(grid: &Grid<usize>, start: Pos) -> SearchResult<Pos> {
+    search::bfs(start, |&p| {
+        let next = grid[p] + 1;
+        grid.neighbours(p)
+            .matching(move |v| *v == next)
+            .map(|n| n.pos)
+    })
+}
@@ -0,0 +1,102 @@
+use lib::{memo::memoize, *};
+use std::{collections::HashMap, fmt::Display};
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Vec<usize>;
+
+    fn parse(input: &str) -> Self::Input {
+        parse::all_ints(input).expect("stone numbers")
+    }
+
+    fn part1(stones: &Self::Input) -> impl Display {
+        SOLVE(stones, 25)
+    }
+
+    fn part2(stones: &Self::Input) -> impl Display {
+        SOLVE(stones, 75)
+    }
+}
+
+/// Strategy used by the solution. Both [`solve_memo`] and [`solve_counts`]
+/// yield the same results, switch this to compare their runtime.
+const SOLVE: fn(&[usize], usize) -> usize = solve_memo;
+
+/// Applies the rules of a single blink to a stone, which results in either
+/// one or two new stones.
+fn blink(stone: usize) -> (usize, Option<usize>) {
+    if stone == 0 {
+        return (1, None);
+    }
+
+    let digits = stone.ilog10() + 1;
+    if digits % 2 == 1 {
+        return (stone * 2024, None);
+    }
+
+    let half = 10usize.pow(digits / 2);
+    (stone / half, Some(stone % half))
+}
+
+/// Counts the resulting stones by recursing into every stone separately,
+/// caching the result for each stone and number of remaining blinks.
+pub fn solve_memo(stones: &[usize], n: usize) -> usize {
+    let mut count = memoize(|count, (stone, rounds_left): (usize, usize)| {
+        if rounds_left == 0 {
+            return 1;
+        }
+
+        let (l, r) = blink(stone);
+        count((l, rounds_left - 1)) + r.map_or(0, |r| count((r, rounds_left - 1)))
+    });
+
+    stones.iter().map(|&stone| count.call((stone, n))).sum()
+}
+
+/// Counts the resulting stones by tracking how often each stone number
+/// occurs, blinking every distinct number only once per round.
+pub fn solve_counts(stones: &[usize], n: usize) -> usize {
+    let mut counts: HashMap<usize, usize> = HashMap::new();
+    for &stone in stones {
+        *counts.entry(stone).or_default() += 1;
+    }
+
+    for _ in 0..n {
+        let mut next = HashMap::with_capacity(counts.len());
+        for (stone, count) in counts {
+            let (l, r) = blink(stone);
+            *next.entry(l).or_default() += count;
+            if let Some(r) = r {
+                *next.entry(r).or_default() += count;
+            }
+        }
+        counts = next;
+    }
+
+    counts.values().sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn blinks() {
+        assert_eq!(blink(0), (1, None));
+        assert_eq!(blink(1), (2024, None));
+        assert_eq!(blink(10), (1, Some(0)));
+        assert_eq!(blink(99), (9, Some(9)));
+        assert_eq!(blink(1000), (10, Some(0)));
+        assert_eq!(blink(999), (2021976, None));
+    }
+
+    #[test]
+    fn strategies_agree() {
+        assert_eq!(solve_memo(&[125, 17], 6), 22);
+        assert_eq!(solve_counts(&[125, 17], 6), 22);
+        assert_eq!(solve_memo(&[125, 17], 25), 55312);
+        assert_eq!(solve_counts(&[125, 17], 25), 55312);
+        assert_eq!(solve_memo(&[125, 17], 75), solve_counts(&[125, 17], 75));
+    }
+}
@@ -0,0 +1,29 @@
+use lib::{region::Segmentation, *};
+use std::fmt::Display;
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Segmentation<char>;
+
+    fn parse(input: &str) -> Self::Input {
+        let grid: Grid<char> = input.parse().expect("grid");
+        region::segment(&grid)
+    }
+
+    fn part1(segmentation: &Self::Input) -> impl Display {
+        segmentation
+            .regions()
+            .iter()
+            .map(|r| r.area() * r.perimeter())
+            .sum::<usize>()
+    }
+
+    fn part2(segmentation: &Self::Input) -> impl Display {
+        segmentation
+            .regions()
+            .iter()
+            .map(|r| r.area() * r.sides())
+            .sum::<usize>()
+    }
+}
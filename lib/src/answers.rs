@@ -0,0 +1,247 @@
+use crate::Part;
+use core::fmt;
+use std::{collections::HashMap, error::Error, path::Path, str::FromStr};
+
+/// Error returned when an answers file can not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnswersError {
+    pub line: usize,
+    pub msg: String,
+}
+
+impl fmt::Display for AnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.msg)
+    }
+}
+
+impl Error for AnswersError {}
+
+/// Known correct answers of a day, usually read from the day's
+/// `answers.toml`.
+///
+/// The file is a small subset of TOML: answers are integers or strings,
+/// grouped by the input they belong to, like `input` or `example`. They can
+/// either be given in tables or with dotted keys.
+///
+/// # Example
+/// ```
+/// # use lib::{Answers, Part};
+/// let answers: Answers = r#"
+/// example.part1 = 143
+///
+/// [input]
+/// part1 = 4790
+/// part2 = "6319" # strings work as well
+/// "#
+/// .parse()
+/// .unwrap();
+///
+/// assert_eq!(answers.get("example", Part::One), Some("143"));
+/// assert_eq!(answers.get("example", Part::Two), None);
+/// assert_eq!(answers.get("input", Part::Two), Some("6319"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    values: HashMap<String, String>,
+}
+
+impl Answers {
+    /// Reads the answers from the file at `path`. A missing file results in
+    /// an empty set of answers.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        match std::fs::read_to_string(path) {
+            Ok(s) => s.parse().map_err(|e| format!("{}: {e}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("failed reading {}: {e}", path.display())),
+        }
+    }
+
+    /// Returns the expected answer of `part` for the given input.
+    pub fn get(&self, input: &str, part: Part) -> Option<&str> {
+        self.values
+            .get(&format!("{input}.part{part}"))
+            .map(String::as_str)
+    }
+
+    /// Compares `answer` to the expected answer of `part` for the given
+    /// input.
+    ///
+    /// # Example
+    /// ```
+    /// # use lib::{Answers, Check, Part};
+    /// let answers: Answers = "example.part1 = 11".parse().unwrap();
+    /// assert_eq!(answers.check("example", Part::One, "11"), Check::Correct);
+    /// assert_eq!(
+    ///     answers.check("example", Part::One, "12"),
+    ///     Check::Wrong { expected: "11".into() }
+    /// );
+    /// assert_eq!(answers.check("input", Part::One, "12"), Check::Unverified);
+    /// ```
+    pub fn check(&self, input: &str, part: Part, answer: &str) -> Check {
+        match self.get(input, part) {
+            None => Check::Unverified,
+            Some(expected) if expected == answer => Check::Correct,
+            Some(expected) => Check::Wrong {
+                expected: expected.to_owned(),
+            },
+        }
+    }
+}
+
+impl FromStr for Answers {
+    type Err = AnswersError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut values = HashMap::new();
+        let mut table = None;
+
+        for (i, line) in s.lines().enumerate() {
+            let err = |msg: &str| AnswersError {
+                line: i + 1,
+                msg: msg.into(),
+            };
+
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(name) = line.strip_prefix('[') {
+                let name = name
+                    .strip_suffix(']')
+                    .ok_or_else(|| err("unclosed table"))?;
+                table = Some(parse_key(name).ok_or_else(|| err("invalid table name"))?);
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| err("expected 'key = value'"))?;
+            let key = parse_key(key).ok_or_else(|| err("invalid key"))?;
+            let value = parse_value(value.trim()).ok_or_else(|| err("invalid value"))?;
+
+            let key = match &table {
+                Some(table) => format!("{table}.{key}"),
+                None => key,
+            };
+            if values.insert(key, value).is_some() {
+                return Err(err("duplicate key"));
+            }
+        }
+
+        Ok(Self { values })
+    }
+}
+
+/// Result of comparing an answer to the expected one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    Correct,
+    Wrong {
+        expected: String,
+    },
+    /// There is no expected answer to compare to, so the answer is neither
+    /// known to be correct nor wrong.
+    Unverified,
+}
+
+impl Check {
+    /// Returns a short machine-readable name of the result.
+    pub fn status(&self) -> &'static str {
+        match self {
+            Check::Correct => "correct",
+            Check::Wrong { .. } => "wrong",
+            Check::Unverified => "unverified",
+        }
+    }
+
+    /// Returns the expected answer if the answer is wrong.
+    pub fn expected(&self) -> Option<&str> {
+        match self {
+            Check::Wrong { expected } => Some(expected),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Check {
+    /// Renders a mark to append to the printed answer.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Check::Correct => write!(f, "✓"),
+            Check::Wrong { expected } => write!(f, "✗ (expected {expected})"),
+            Check::Unverified => write!(f, "(unverified)"),
+        }
+    }
+}
+
+/// Formats the answer of `part` like `Part 1 Solution: 42 ✓`.
+pub fn format_answer(part: Part, answer: &str, check: &Check) -> String {
+    format!("Part {part} Solution: {answer} {check}")
+}
+
+/// Removes a trailing `#` comment, ignoring `#` within strings.
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+/// Parses a bare, possibly dotted, key.
+fn parse_key(key: &str) -> Option<String> {
+    let parts: Vec<_> = key.split('.').map(str::trim).collect();
+    parts
+        .iter()
+        .all(|p| {
+            !p.is_empty()
+                && p.chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        })
+        .then(|| parts.join("."))
+}
+
+/// Parses an integer or a basic string without escapes.
+fn parse_value(value: &str) -> Option<String> {
+    if let Some(s) = value.strip_prefix('"') {
+        let s = s.strip_suffix('"')?;
+        return (!s.contains(['"', '\\'])).then(|| s.to_owned());
+    }
+
+    let digits = value.strip_prefix(['-', '+']).unwrap_or(value);
+    (!digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit() || c == '_'))
+        .then(|| value.trim_start_matches('+').replace('_', ""))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_errors() {
+        let err = |s: &str| s.parse::<Answers>().unwrap_err();
+
+        assert_eq!(err("[input]\npart1 = 1\npart1 = 2").line, 3);
+        assert_eq!(err("[input\npart1 = 1").line, 1);
+        assert_eq!(err("part1 = abc").msg, "invalid value");
+        assert_eq!(err("part1 = \"unclosed").msg, "invalid value");
+        assert_eq!(err("part1").msg, "expected 'key = value'");
+        assert_eq!(err("a b = 1").msg, "invalid key");
+    }
+
+    #[test]
+    fn test_values() {
+        let answers: Answers = "[input]\npart1 = 1_000 # comment\npart2 = \"a#b\""
+            .parse()
+            .unwrap();
+        assert_eq!(answers.get("input", Part::One), Some("1000"));
+        assert_eq!(answers.get("input", Part::Two), Some("a#b"));
+    }
+}
@@ -0,0 +1,155 @@
+//! Timing and benchmarking of solution phases.
+
+use core::fmt;
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+/// Calls `f` and returns its result together with the time it took.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let res = f();
+    (res, start.elapsed())
+}
+
+/// Calls `f` `warmup` times without measuring and then `iterations` times,
+/// measuring each call.
+///
+/// # Example
+/// ```
+/// # use lib::bench::bench;
+/// let stats = bench(2, 10, || (0..1000u64).sum::<u64>());
+/// assert_eq!(stats.iterations, 10);
+/// assert!(stats.min <= stats.median && stats.median <= stats.max);
+/// ```
+pub fn bench<T>(warmup: usize, iterations: usize, mut f: impl FnMut() -> T) -> Stats {
+    for _ in 0..warmup {
+        black_box(f());
+    }
+
+    let samples: Vec<_> = (0..iterations)
+        .map(|_| measure(|| black_box(f())).1)
+        .collect();
+
+    Stats::from_samples(&samples)
+}
+
+/// Summary statistics over a set of timing samples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Stats {
+    pub iterations: usize,
+    pub min: Duration,
+    pub max: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Computes the statistics of `samples`. For an even number of samples,
+    /// the median is the mean of the two middle ones.
+    ///
+    /// # Example
+    /// ```
+    /// # use lib::bench::Stats;
+    /// # use std::time::Duration;
+    /// let ms = Duration::from_millis;
+    /// let stats = Stats::from_samples(&[ms(4), ms(1), ms(3), ms(2), ms(10)]);
+    /// assert_eq!(stats.min, ms(1));
+    /// assert_eq!(stats.median, ms(3));
+    /// assert_eq!(stats.mean, ms(4));
+    /// assert_eq!(stats.stddev.as_micros(), 3162);
+    /// ```
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let n = sorted.len();
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        } else {
+            sorted[n / 2]
+        };
+
+        let mean = sorted.iter().map(Duration::as_nanos).sum::<u128>() / n as u128;
+        let variance = sorted
+            .iter()
+            .map(|d| (d.as_nanos() as f64 - mean as f64).powi(2))
+            .sum::<f64>()
+            / n as f64;
+
+        Self {
+            iterations: n,
+            min: sorted[0],
+            max: sorted[n - 1],
+            median,
+            mean: Duration::from_nanos(mean as u64),
+            stddev: Duration::from_nanos(variance.sqrt() as u64),
+        }
+    }
+
+    /// Returns by how many percent the median changed compared to
+    /// `baseline`, where positive values mean slower.
+    ///
+    /// # Example
+    /// ```
+    /// # use lib::bench::Stats;
+    /// # use std::time::Duration;
+    /// let ms = Duration::from_millis;
+    /// let before = Stats::from_samples(&[ms(4)]);
+    /// let after = Stats::from_samples(&[ms(5)]);
+    /// assert_eq!(after.change(&before), 25.0);
+    /// assert_eq!(before.change(&after), -20.0);
+    /// ```
+    pub fn change(&self, baseline: &Stats) -> f64 {
+        let base = baseline.median.as_nanos() as f64;
+        if base == 0.0 {
+            return 0.0;
+        }
+        (self.median.as_nanos() as f64 - base) / base * 100.0
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "min {}  median {}  mean {}  stddev {}  ({} runs)",
+            Short(self.min),
+            Short(self.median),
+            Short(self.mean),
+            Short(self.stddev),
+            self.iterations
+        )
+    }
+}
+
+/// Displays a duration rounded to two decimals in the most fitting unit.
+///
+/// # Example
+/// ```
+/// # use lib::bench::Short;
+/// # use std::time::Duration;
+/// assert_eq!(Short(Duration::from_nanos(1_234_567)).to_string(), "1.23ms");
+/// assert_eq!(Short(Duration::from_nanos(950)).to_string(), "950ns");
+/// assert_eq!(Short(Duration::from_secs(3)).to_string(), "3.00s");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Short(pub Duration);
+
+impl fmt::Display for Short {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ns = self.0.as_nanos() as f64;
+        match ns {
+            _ if ns < 1e3 => write!(f, "{ns}ns"),
+            _ if ns < 1e6 => write!(f, "{:.2}µs", ns / 1e3),
+            _ if ns < 1e9 => write!(f, "{:.2}ms", ns / 1e6),
+            _ => write!(f, "{:.2}s", ns / 1e9),
+        }
+    }
+}
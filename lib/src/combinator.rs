@@ -0,0 +1,391 @@
+//! Small parser combinator toolkit working on a [`Cursor`] over a `&str`.
+//!
+//! Parsers are plain functions or closures taking a `&mut Cursor` and
+//! returning a [`PResult`]. A failing parser leaves the cursor where it was,
+//! so alternatives can be tried without any manual backtracking.
+//!
+//! # Example
+//! ```
+//! use lib::combinator::*;
+//!
+//! let list = delimited(tag("["), sep_by(number::<u32>, tag(",")), tag("]"));
+//! assert_eq!(parse("[1,2,3]", &list), Ok(vec![1, 2, 3]));
+//!
+//! let err = parse("[1,2;3]", &list).unwrap_err();
+//! assert_eq!((err.line, err.column), (1, 5));
+//! assert_eq!(err.to_string(), "line 1, column 5: expected \"]\"");
+//! ```
+
+use core::fmt;
+use std::{error::Error, str::FromStr};
+
+/// Error of a failed parser, pointing at the position in the input where
+/// it failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line of the position.
+    pub line: usize,
+    /// 1-based column of the position, counted in characters.
+    pub column: usize,
+    /// Description of what has been expected at the position.
+    pub expected: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}",
+            self.line, self.column, self.expected
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// Result of a parser.
+pub type PResult<T> = Result<T, ParseError>;
+
+/// Position in the input being parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cursor<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self { input, pos: 0 }
+    }
+
+    /// Returns the remaining input.
+    pub fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    /// Returns the byte offset of the position in the input.
+    pub fn offset(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns true if the whole input has been consumed.
+    pub fn is_empty(&self) -> bool {
+        self.pos == self.input.len()
+    }
+
+    /// Consumes the next `n` bytes and returns them.
+    ///
+    /// # Panics
+    /// Panics if `n` does not end on a character boundary of the remaining
+    /// input.
+    pub fn advance(&mut self, n: usize) -> &'a str {
+        let consumed = &self.rest()[..n];
+        self.pos += n;
+        consumed
+    }
+
+    /// Returns an error at the current position.
+    pub fn error(&self, expected: impl Into<String>) -> ParseError {
+        let before = &self.input[..self.pos];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            expected: expected.into(),
+        }
+    }
+
+    /// Applies `parser`, restoring the position if it fails.
+    pub fn attempt<T>(&mut self, parser: &impl Parser<'a, T>) -> PResult<T> {
+        let start = self.pos;
+        let res = parser.parse(self);
+        if res.is_err() {
+            self.pos = start;
+        }
+        res
+    }
+}
+
+/// A parser producing values of type `T`. Implemented for all functions
+/// and closures taking a `&mut Cursor`.
+pub trait Parser<'a, T> {
+    fn parse(&self, cur: &mut Cursor<'a>) -> PResult<T>;
+}
+
+impl<'a, T, F> Parser<'a, T> for F
+where
+    F: Fn(&mut Cursor<'a>) -> PResult<T>,
+{
+    fn parse(&self, cur: &mut Cursor<'a>) -> PResult<T> {
+        self(cur)
+    }
+}
+
+/// Parses all of `input` with `parser`.
+pub fn parse<'a, T>(input: &'a str, parser: &impl Parser<'a, T>) -> PResult<T> {
+    let mut cur = Cursor::new(input);
+    let res = cur.attempt(parser)?;
+    if !cur.is_empty() {
+        return Err(cur.error("end of input"));
+    }
+    Ok(res)
+}
+
+/// Returns all matches of `parser` in `input`, skipping any text in between
+/// which does not match.
+///
+/// # Example
+/// ```
+/// # use lib::combinator::*;
+/// let found = scan("a12b;3_x45", &number::<u32>);
+/// assert_eq!(found, [12, 3, 45]);
+/// ```
+pub fn scan<'a, T>(input: &'a str, parser: &impl Parser<'a, T>) -> Vec<T> {
+    let mut cur = Cursor::new(input);
+    let mut res = vec![];
+    while !cur.is_empty() {
+        match cur.attempt(parser) {
+            Ok(v) => res.push(v),
+            Err(_) => {
+                let skip = cur.rest().chars().next().map_or(1, char::len_utf8);
+                cur.advance(skip);
+            }
+        }
+    }
+    res
+}
+
+/// Matches the literal `expected`.
+pub fn tag<'a>(expected: &str) -> impl Fn(&mut Cursor<'a>) -> PResult<&'a str> + '_ {
+    move |cur| {
+        if cur.rest().starts_with(expected) {
+            Ok(cur.advance(expected.len()))
+        } else {
+            Err(cur.error(format!("{expected:?}")))
+        }
+    }
+}
+
+/// Parses a decimal integer with an optional leading `-`.
+pub fn number<T: FromStr>(cur: &mut Cursor) -> PResult<T> {
+    let rest = cur.rest();
+    let sign = usize::from(rest.starts_with('-'));
+    let len = sign + rest[sign..].bytes().take_while(u8::is_ascii_digit).count();
+
+    match rest[..len].parse() {
+        Ok(n) if len > sign => {
+            cur.advance(len);
+            Ok(n)
+        }
+        _ => Err(cur.error("a number")),
+    }
+}
+
+/// Applies `parser` and maps its result with `f`.
+pub fn map<'a, T, U>(
+    parser: impl Parser<'a, T>,
+    f: impl Fn(T) -> U,
+) -> impl Fn(&mut Cursor<'a>) -> PResult<U> {
+    move |cur| cur.attempt(&parser).map(&f)
+}
+
+/// Applies `parser` and returns its result together with the byte offset
+/// in the input where it started.
+///
+/// # Example
+/// ```
+/// # use lib::combinator::*;
+/// let found = scan("ab12  3", &spanned(number::<u32>));
+/// assert_eq!(found, [(2, 12), (6, 3)]);
+/// ```
+pub fn spanned<'a, T>(
+    parser: impl Parser<'a, T>,
+) -> impl Fn(&mut Cursor<'a>) -> PResult<(usize, T)> {
+    move |cur| {
+        let offset = cur.offset();
+        cur.attempt(&parser).map(|v| (offset, v))
+    }
+}
+
+/// Applies `open`, `parser` and `close` in sequence and returns the result
+/// of `parser`.
+pub fn delimited<'a, O, T, C>(
+    open: impl Parser<'a, O>,
+    parser: impl Parser<'a, T>,
+    close: impl Parser<'a, C>,
+) -> impl Fn(&mut Cursor<'a>) -> PResult<T> {
+    move |cur| {
+        cur.attempt(&|cur: &mut Cursor<'a>| {
+            open.parse(cur)?;
+            let res = parser.parse(cur)?;
+            close.parse(cur)?;
+            Ok(res)
+        })
+    }
+}
+
+/// Applies `first`, `sep` and `second` in sequence and returns the results
+/// of `first` and `second`.
+pub fn separated_pair<'a, A, S, B>(
+    first: impl Parser<'a, A>,
+    sep: impl Parser<'a, S>,
+    second: impl Parser<'a, B>,
+) -> impl Fn(&mut Cursor<'a>) -> PResult<(A, B)> {
+    move |cur| {
+        cur.attempt(&|cur: &mut Cursor<'a>| {
+            let a = first.parse(cur)?;
+            sep.parse(cur)?;
+            Ok((a, second.parse(cur)?))
+        })
+    }
+}
+
+/// Applies `parser` as often as possible, which might be zero times. Stops
+/// as well once `parser` succeeds without consuming any input.
+pub fn many<'a, T>(parser: impl Parser<'a, T>) -> impl Fn(&mut Cursor<'a>) -> PResult<Vec<T>> {
+    move |cur| {
+        let mut res = vec![];
+        loop {
+            let start = cur.pos;
+            match cur.attempt(&parser) {
+                Ok(v) => res.push(v),
+                Err(_) => break,
+            }
+            if cur.pos == start {
+                break;
+            }
+        }
+        Ok(res)
+    }
+}
+
+/// Parses zero or more `parser` separated by `sep`. A trailing separator is
+/// not consumed.
+pub fn sep_by<'a, T, S>(
+    parser: impl Parser<'a, T>,
+    sep: impl Parser<'a, S>,
+) -> impl Fn(&mut Cursor<'a>) -> PResult<Vec<T>> {
+    move |cur| {
+        let mut res = vec![];
+        let Ok(first) = cur.attempt(&parser) else {
+            return Ok(res);
+        };
+        res.push(first);
+
+        let next = |cur: &mut Cursor<'a>| {
+            sep.parse(cur)?;
+            parser.parse(cur)
+        };
+        while let Ok(v) = cur.attempt(&next) {
+            res.push(v);
+        }
+        Ok(res)
+    }
+}
+
+/// A tuple of parsers producing the same type, tried in order by [`alt`].
+pub trait Choice<'a, T> {
+    fn choose(&self, cur: &mut Cursor<'a>) -> PResult<T>;
+}
+
+macro_rules! impl_choice {
+    ($($p: ident),+) => {
+        impl<'a, T, $($p: Parser<'a, T>),+> Choice<'a, T> for ($($p,)+) {
+            #[allow(non_snake_case)]
+            fn choose(&self, cur: &mut Cursor<'a>) -> PResult<T> {
+                let ($($p,)+) = self;
+                let mut expected = vec![];
+                $(
+                    match cur.attempt($p) {
+                        Ok(v) => return Ok(v),
+                        Err(err) => expected.push(err.expected),
+                    }
+                )+
+                Err(cur.error(expected.join(" or ")))
+            }
+        }
+    };
+}
+
+impl_choice!(A, B);
+impl_choice!(A, B, C);
+impl_choice!(A, B, C, D);
+impl_choice!(A, B, C, D, E);
+
+/// Returns the result of the first of the given parsers which succeeds.
+///
+/// # Example
+/// ```
+/// # use lib::combinator::*;
+/// let bit = alt((map(tag("on"), |_| true), map(tag("off"), |_| false)));
+/// assert_eq!(parse("off", &bit), Ok(false));
+/// assert_eq!(
+///     parse("of", &bit).unwrap_err().expected,
+///     "\"on\" or \"off\""
+/// );
+/// ```
+pub fn alt<'a, T>(choices: impl Choice<'a, T>) -> impl Fn(&mut Cursor<'a>) -> PResult<T> {
+    move |cur| choices.choose(cur)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_number() {
+        assert_eq!(parse("-42", &number::<i32>), Ok(-42));
+        assert_eq!(parse("007", &number::<u8>), Ok(7));
+        assert!(parse("-", &number::<i32>).is_err());
+        assert!(parse("-1", &number::<u32>).is_err());
+        assert!(parse("300", &number::<u8>).is_err());
+        assert!(parse("", &number::<u8>).is_err());
+    }
+
+    #[test]
+    fn test_failing_parsers_do_not_consume() {
+        let pair = separated_pair(number::<u32>, tag(","), number::<u32>);
+        let mut cur = Cursor::new("1,x");
+        assert!(pair.parse(&mut cur).is_err());
+        assert_eq!(cur.rest(), "1,x");
+
+        let list = sep_by(number::<u32>, tag(","));
+        let mut cur = Cursor::new("1,2,;");
+        assert_eq!(list.parse(&mut cur), Ok(vec![1, 2]));
+        assert_eq!(cur.rest(), ",;");
+
+        let words = many(alt((tag("ab"), tag("a"))));
+        let mut cur = Cursor::new("abaab?");
+        assert_eq!(words.parse(&mut cur), Ok(vec!["ab", "a", "ab"]));
+        assert_eq!(cur.rest(), "?");
+
+        let empty = many(tag(""));
+        assert_eq!(parse("", &empty), Ok(vec![""]));
+    }
+
+    #[test]
+    fn test_error_location() {
+        let line = delimited(tag("<"), number::<u32>, tag(">\n"));
+        let lines = many(line);
+
+        let err = parse("<1>\n<2>\n<3]\n", &lines).unwrap_err();
+        assert_eq!((err.line, err.column), (3, 1));
+        assert_eq!(err.expected, "end of input");
+
+        let err = parse("äö<x>", &delimited(tag("äö<"), number::<u32>, tag(">"))).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 4));
+    }
+
+    #[test]
+    fn test_scan() {
+        let mul = delimited(
+            tag("mul("),
+            separated_pair(number::<u32>, tag(","), number::<u32>),
+            tag(")"),
+        );
+        assert_eq!(
+            scan("mul(2,3)mul(4,5]xmul(mul(1,2)ü", &mul),
+            [(2, 3), (1, 2)]
+        );
+    }
+}
@@ -0,0 +1,434 @@
+//! Cycle detection for deterministic state machines.
+//!
+//! All detectors take an initial state and a `step` function which returns
+//! the successor of a state, or `None` if the machine terminates. Because the
+//! machine is deterministic, it either terminates or eventually enters a
+//! cycle, which is described by a [`Cycle`].
+
+use crate::{Direction, Grid, Pos};
+use std::{collections::HashMap, hash::Hash};
+
+/// Describes the cycle of a deterministic state machine.
+///
+/// The state after `start` steps is the first state which is part of the
+/// cycle, and the state after `start + len` steps is equal to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// Number of steps until the first state of the cycle is reached.
+    pub start: usize,
+    /// Number of steps of one iteration of the cycle.
+    pub len: usize,
+}
+
+impl Cycle {
+    /// Returns the smallest number of steps which results in the same state
+    /// as taking `n` steps.
+    ///
+    /// # Example
+    /// ```
+    /// # use lib::cycle::Cycle;
+    /// let cycle = Cycle { start: 2, len: 3 };
+    /// assert_eq!(cycle.equivalent_step(1), 1);
+    /// assert_eq!(cycle.equivalent_step(5), 2);
+    /// assert_eq!(cycle.equivalent_step(1_000_000), 4);
+    /// ```
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.len
+        }
+    }
+
+    /// Returns the state after `n` steps starting from `init` by only
+    /// simulating [`Cycle::equivalent_step`] steps.
+    ///
+    /// # Panics
+    /// Panics if `step` terminates before, which means that the cycle does
+    /// not belong to the given state machine.
+    ///
+    /// # Example
+    /// ```
+    /// # use lib::cycle::brent;
+    /// let step = |s: &u64| Some((s * s + 1) % 255);
+    /// let cycle = brent(3, step).unwrap();
+    ///
+    /// let mut s = 3;
+    /// for _ in 0..1000 {
+    ///     s = step(&s).unwrap();
+    /// }
+    /// assert_eq!(cycle.extrapolate(3, step, 1000), s);
+    /// ```
+    pub fn extrapolate<S>(&self, init: S, mut step: impl FnMut(&S) -> Option<S>, n: usize) -> S {
+        let mut s = init;
+        for _ in 0..self.equivalent_step(n) {
+            s = step(&s).expect("state machine terminated inside of a cycle");
+        }
+        s
+    }
+}
+
+/// Detects a cycle using Floyd's "tortoise and hare" algorithm with constant
+/// memory. Returns `None` if the state machine terminates.
+///
+/// # Example
+/// ```
+/// # use lib::cycle::{floyd, Cycle};
+/// // 0 -> 1 -> 2 -> 3 -> 4 -> 2 -> ...
+/// let cycle = floyd(0, |&s| Some(if s == 4 { 2 } else { s + 1 }));
+/// assert_eq!(cycle, Some(Cycle { start: 2, len: 3 }));
+///
+/// assert_eq!(floyd(0, |&s| (s < 10).then_some(s + 1)), None);
+/// ```
+pub fn floyd<S>(init: S, mut step: impl FnMut(&S) -> Option<S>) -> Option<Cycle>
+where
+    S: Clone + PartialEq,
+{
+    let mut tortoise = step(&init)?;
+    let mut hare = step(&tortoise)?;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        let half = step(&hare)?;
+        hare = step(&half)?;
+    }
+
+    let mut start = 0;
+    tortoise = init;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = step(&hare)?;
+        start += 1;
+    }
+
+    let mut len = 1;
+    hare = step(&tortoise)?;
+    while tortoise != hare {
+        hare = step(&hare)?;
+        len += 1;
+    }
+
+    Some(Cycle { start, len })
+}
+
+/// Detects a cycle using Brent's algorithm with constant memory, which
+/// usually needs fewer calls to `step` than [`floyd`]. Returns `None` if the
+/// state machine terminates.
+///
+/// # Example
+/// ```
+/// # use lib::cycle::{brent, Cycle};
+/// let cycle = brent(0, |&s| Some(if s == 4 { 2 } else { s + 1 }));
+/// assert_eq!(cycle, Some(Cycle { start: 2, len: 3 }));
+/// ```
+pub fn brent<S>(init: S, mut step: impl FnMut(&S) -> Option<S>) -> Option<Cycle>
+where
+    S: Clone + PartialEq,
+{
+    let mut power = 1;
+    let mut len = 1;
+    let mut tortoise = init.clone();
+    let mut hare = step(&init)?;
+    while tortoise != hare {
+        if power == len {
+            tortoise = hare.clone();
+            power *= 2;
+            len = 0;
+        }
+        hare = step(&hare)?;
+        len += 1;
+    }
+
+    tortoise = init.clone();
+    hare = init;
+    for _ in 0..len {
+        hare = step(&hare)?;
+    }
+
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = step(&hare)?;
+        start += 1;
+    }
+
+    Some(Cycle { start, len })
+}
+
+/// Detects a cycle by remembering every visited state. This needs memory
+/// for every state until the cycle closes, but calls `step` exactly
+/// `start + len` times. Returns `None` if the state machine terminates.
+///
+/// # Example
+/// ```
+/// # use lib::cycle::{hashed, Cycle};
+/// let cycle = hashed(0, |&s| Some(if s == 4 { 2 } else { s + 1 }));
+/// assert_eq!(cycle, Some(Cycle { start: 2, len: 3 }));
+/// ```
+pub fn hashed<S>(init: S, mut step: impl FnMut(&S) -> Option<S>) -> Option<Cycle>
+where
+    S: Clone + Hash + Eq,
+{
+    let mut seen = HashMap::new();
+    let mut s = init;
+    for i in 0.. {
+        if let Some(&start) = seen.get(&s) {
+            return Some(Cycle {
+                start,
+                len: i - start,
+            });
+        }
+        let next = step(&s)?;
+        seen.insert(s, i);
+        s = next;
+    }
+    unreachable!()
+}
+
+/// Where a [`GridWalker`] ends up when walking straight from a state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Jump {
+    /// The walker stops at the given position in front of a wall.
+    Stop(Pos),
+    /// The walker leaves the grid.
+    Exit,
+}
+
+/// Simulates a walker on a grid which goes straight until it hits a wall,
+/// where it turns clockwise, until it leaves the grid.
+///
+/// On construction, a jump table is computed which maps every state to the
+/// position in front of the next wall, so that each simulated step skips a
+/// whole straight segment. This makes it cheap to check whether placing a
+/// single additional wall causes the walker to loop forever.
+///
+/// # Example
+/// ```
+/// # use lib::{Grid, Pos, Direction, cycle::GridWalker};
+/// let grid: Grid<char> = "\
+/// .#..
+/// ...#
+/// ....
+/// ..#.".parse().unwrap();
+///
+/// let walker = GridWalker::new(&grid, |c| *c == '#');
+/// let start = (Pos { x: 1, y: 3 }, Direction::Up);
+///
+/// assert_eq!(walker.step(start, None), Some((Pos { x: 1, y: 1 }, Direction::Right)));
+/// assert!(!walker.is_loop(start, None));
+/// assert!(walker.is_loop(start, Some(Pos { x: 0, y: 2 })));
+/// ```
+#[derive(Debug, Clone)]
+pub struct GridWalker {
+    walls: Grid<bool>,
+    jumps: Vec<Jump>,
+}
+
+impl GridWalker {
+    /// Creates a new walker for the given grid, where every cell matching
+    /// `is_wall` blocks the way.
+    pub fn new<T>(grid: &Grid<T>, is_wall: impl Fn(&T) -> bool) -> Self {
+        let walls = grid.map(is_wall);
+        let mut jumps = vec![Jump::Exit; walls.width() * walls.height() * 4];
+
+        for dir in Direction::all() {
+            // Cells are visited in an order where the next cell in `dir` has
+            // always been computed before.
+            let mut positions: Vec<_> = walls.positions().collect();
+            if matches!(dir, Direction::Down | Direction::Right) {
+                positions.reverse();
+            }
+
+            for pos in positions {
+                let next = pos.mv(dir);
+                let jump = match walls.get(next) {
+                    None => Jump::Exit,
+                    Some(true) => Jump::Stop(pos),
+                    Some(false) => jumps[state_index(&walls, next, dir).unwrap()],
+                };
+                jumps[state_index(&walls, pos, dir).unwrap()] = jump;
+            }
+        }
+
+        Self { walls, jumps }
+    }
+
+    /// Walks straight from `pos` into `dir` up to the next wall and turns
+    /// clockwise there. An additional wall can be placed at `extra_wall`,
+    /// which is ignored if it lies outside of the grid. Returns `None` if the
+    /// walker leaves the grid or `pos` is outside of it.
+    pub fn step(
+        &self,
+        (pos, dir): (Pos, Direction),
+        extra_wall: Option<Pos>,
+    ) -> Option<(Pos, Direction)> {
+        let jump = self.jumps[state_index(&self.walls, pos, dir)?];
+
+        if let Some(wall) = extra_wall.filter(|&w| self.walls.get(w).is_some()) {
+            let to_wall = wall - pos;
+            let step = Pos::from(dir);
+            let k = to_wall.x * step.x + to_wall.y * step.y;
+            let on_ray = k > 0 && step * Pos { x: k, y: k } == to_wall;
+            let before_jump = match jump {
+                Jump::Stop(stop) => k <= pos.manhattan_distance(stop) as isize,
+                Jump::Exit => true,
+            };
+            if on_ray && before_jump {
+                return Some((wall - step, dir.turn_cw()));
+            }
+        }
+
+        match jump {
+            Jump::Stop(stop) => Some((stop, dir.turn_cw())),
+            Jump::Exit => None,
+        }
+    }
+
+    /// Returns true if the walker starting at the given state never leaves
+    /// the grid. An additional wall can be placed at `extra_wall`, see
+    /// [`GridWalker::step`].
+    pub fn is_loop(&self, start: (Pos, Direction), extra_wall: Option<Pos>) -> bool {
+        brent(start, |&s| self.step(s, extra_wall)).is_some()
+    }
+
+    /// Returns the number of distinct states of a walker in the grid.
+    pub fn states(&self) -> usize {
+        self.walls.width() * self.walls.height() * 4
+    }
+
+    /// Returns a unique index in `0..self.states()` for the given state, or
+    /// `None` if its position is outside of the grid.
+    pub fn state_index(&self, (pos, dir): (Pos, Direction)) -> Option<usize> {
+        state_index(&self.walls, pos, dir)
+    }
+}
+
+fn state_index<T>(grid: &Grid<T>, pos: Pos, dir: Direction) -> Option<usize> {
+    if !grid.is_in_bounds(pos) {
+        return None;
+    }
+    let d = match dir {
+        Direction::Up => 0,
+        Direction::Right => 1,
+        Direction::Down => 2,
+        Direction::Left => 3,
+    };
+    Some((pos.y as usize * grid.width() + pos.x as usize) * 4 + d)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_state_index() {
+        let walker = GridWalker::new(&Grid::filled(3, 2, '.'), |c| *c == '#');
+        let mut seen = vec![false; walker.states()];
+        for pos in walker.walls.positions() {
+            for dir in Direction::all() {
+                let idx = walker.state_index((pos, dir)).unwrap();
+                assert!(!seen[idx], "{pos} {dir:?} collides");
+                seen[idx] = true;
+            }
+        }
+        assert!(seen.iter().all(|s| *s));
+
+        for pos in [Pos { x: -1, y: 0 }, Pos { x: 3, y: 0 }, Pos { x: 0, y: 2 }] {
+            assert_eq!(walker.state_index((pos, Direction::Up)), None);
+        }
+    }
+
+    #[test]
+    fn test_detectors_agree() {
+        for init in 0..50u64 {
+            let step = |s: &u64| Some((s * s + 7) % 1009);
+            let expected = hashed(init, step);
+            assert!(expected.is_some());
+            assert_eq!(floyd(init, step), expected);
+            assert_eq!(brent(init, step), expected);
+        }
+    }
+
+    #[test]
+    fn test_self_loop() {
+        let expected = Some(Cycle { start: 0, len: 1 });
+        assert_eq!(floyd(1, |&s| Some(s)), expected);
+        assert_eq!(brent(1, |&s| Some(s)), expected);
+        assert_eq!(hashed(1, |&s| Some(s)), expected);
+    }
+
+    #[test]
+    fn test_walker_matches_simulation() {
+        let grid: Grid<char> = "\
+....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#........
+........#.
+#.........
+......#..."
+            .parse()
+            .unwrap();
+        let start = (Pos { x: 4, y: 6 }, Direction::Up);
+        let walker = GridWalker::new(&grid, |c| *c == '#');
+
+        let simulate = |wall: Pos| {
+            hashed(start, |&(pos, dir)| {
+                let next = pos.mv(dir);
+                match grid.get(next) {
+                    None => None,
+                    Some('#') => Some((pos, dir.turn_cw())),
+                    Some(_) if next == wall => Some((pos, dir.turn_cw())),
+                    Some(_) => Some((next, dir)),
+                }
+            })
+            .is_some()
+        };
+
+        let mut loops = 0;
+        for wall in grid.positions() {
+            if wall == start.0 || grid[wall] == '#' {
+                continue;
+            }
+            let is_loop = walker.is_loop(start, Some(wall));
+            assert_eq!(is_loop, simulate(wall), "wall at {wall}");
+            loops += is_loop as usize;
+        }
+        assert_eq!(loops, 6);
+    }
+
+    #[test]
+    fn test_walker_ignores_walls_outside_of_grid() {
+        let grid: Grid<char> = "\
+.#..
+...#
+....
+..#."
+            .parse()
+            .unwrap();
+        let walker = GridWalker::new(&grid, |c| *c == '#');
+        let start = (Pos { x: 0, y: 2 }, Direction::Up);
+
+        for wall in [
+            Pos { x: 0, y: -1 },
+            Pos { x: 0, y: -2 },
+            Pos { x: 0, y: -9 },
+        ] {
+            assert_eq!(walker.step(start, Some(wall)), None, "wall at {wall}");
+            assert!(!walker.is_loop(start, Some(wall)), "wall at {wall}");
+        }
+        assert_eq!(
+            walker.step((Pos { x: 4, y: 0 }, Direction::Left), None),
+            None
+        );
+        assert_eq!(
+            walker.step(
+                (Pos { x: 2, y: 1 }, Direction::Right),
+                Some(Pos { x: 5, y: 1 })
+            ),
+            Some((Pos { x: 2, y: 1 }, Direction::Down))
+        );
+    }
+}
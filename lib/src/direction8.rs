@@ -0,0 +1,160 @@
+use crate::{pos::Pos, Direction};
+use core::fmt;
+use std::ops::Mul;
+
+/// Direction8 represents one of the eight directions pointing to the neighbours
+/// of a cell in a grid, including diagonals.
+///
+/// As with [`Direction`], "up" means going negative in the y-axis.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    /// Returns an array of all available directions in clockwise order,
+    /// starting with [`Direction8::Up`].
+    pub fn all() -> [Self; 8] {
+        [
+            Self::Up,
+            Self::UpRight,
+            Self::Right,
+            Self::DownRight,
+            Self::Down,
+            Self::DownLeft,
+            Self::Left,
+            Self::UpLeft,
+        ]
+    }
+
+    /// Returns an array of all diagonal directions in clockwise order,
+    /// starting with [`Direction8::UpRight`].
+    pub fn diagonals() -> [Self; 4] {
+        [Self::UpRight, Self::DownRight, Self::DownLeft, Self::UpLeft]
+    }
+
+    /// Returns true if the direction is a diagonal direction.
+    ///
+    /// # Example
+    /// ```
+    /// # use lib::Direction8;
+    /// assert!(Direction8::UpLeft.is_diagonal());
+    /// assert!(!Direction8::Left.is_diagonal());
+    /// ```
+    pub fn is_diagonal(&self) -> bool {
+        matches!(
+            self,
+            Self::UpRight | Self::DownRight | Self::DownLeft | Self::UpLeft
+        )
+    }
+
+    /// Reverses a direction.
+    ///
+    /// # Example
+    /// ```
+    /// # use lib::Direction8;
+    /// assert_eq!(Direction8::Up.reverse(), Direction8::Down);
+    /// assert_eq!(Direction8::UpRight.reverse(), Direction8::DownLeft);
+    /// assert_eq!(Direction8::UpLeft.reverse(), Direction8::DownRight);
+    /// ```
+    pub fn reverse(&self) -> Direction8 {
+        self.turn_cw().turn_cw().turn_cw().turn_cw()
+    }
+
+    /// Turns the direction clockwise by 45°.
+    ///
+    /// # Example
+    /// ```
+    /// # use lib::Direction8;
+    /// assert_eq!(Direction8::Up.turn_cw(), Direction8::UpRight);
+    /// assert_eq!(Direction8::UpLeft.turn_cw(), Direction8::Up);
+    /// ```
+    pub fn turn_cw(&self) -> Direction8 {
+        match self {
+            Self::Up => Self::UpRight,
+            Self::UpRight => Self::Right,
+            Self::Right => Self::DownRight,
+            Self::DownRight => Self::Down,
+            Self::Down => Self::DownLeft,
+            Self::DownLeft => Self::Left,
+            Self::Left => Self::UpLeft,
+            Self::UpLeft => Self::Up,
+        }
+    }
+
+    /// Turns the direction counter-clockwise by 45°.
+    ///
+    /// # Example
+    /// ```
+    /// # use lib::Direction8;
+    /// assert_eq!(Direction8::Up.turn_ccw(), Direction8::UpLeft);
+    /// assert_eq!(Direction8::UpRight.turn_ccw(), Direction8::Up);
+    /// ```
+    pub fn turn_ccw(&self) -> Direction8 {
+        self.reverse().turn_cw().turn_cw().turn_cw()
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(v: Direction) -> Self {
+        match v {
+            Direction::Up => Direction8::Up,
+            Direction::Down => Direction8::Down,
+            Direction::Left => Direction8::Left,
+            Direction::Right => Direction8::Right,
+        }
+    }
+}
+
+impl From<Direction8> for Pos {
+    fn from(v: Direction8) -> Self {
+        match v {
+            Direction8::Up => Pos { x: 0, y: -1 },
+            Direction8::UpRight => Pos { x: 1, y: -1 },
+            Direction8::Right => Pos { x: 1, y: 0 },
+            Direction8::DownRight => Pos { x: 1, y: 1 },
+            Direction8::Down => Pos { x: 0, y: 1 },
+            Direction8::DownLeft => Pos { x: -1, y: 1 },
+            Direction8::Left => Pos { x: -1, y: 0 },
+            Direction8::UpLeft => Pos { x: -1, y: -1 },
+        }
+    }
+}
+
+impl fmt::Display for Direction8 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Direction8::Up => write!(f, "↑"),
+            Direction8::UpRight => write!(f, "↗"),
+            Direction8::Right => write!(f, "→"),
+            Direction8::DownRight => write!(f, "↘"),
+            Direction8::Down => write!(f, "↓"),
+            Direction8::DownLeft => write!(f, "↙"),
+            Direction8::Left => write!(f, "←"),
+            Direction8::UpLeft => write!(f, "↖"),
+        }
+    }
+}
+
+impl Mul<isize> for Direction8 {
+    type Output = Pos;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Pos::from(self) * Pos { x: rhs, y: rhs }
+    }
+}
+
+impl Mul<usize> for Direction8 {
+    type Output = Pos;
+
+    fn mul(self, rhs: usize) -> Self::Output {
+        self * rhs as isize
+    }
+}
@@ -0,0 +1,351 @@
+//! Solver for equations of the form `target = a ? b ? c ...`, where every
+//! `?` can be any of a given set of binary operators, which are evaluated
+//! strictly left-to-right.
+
+use core::fmt;
+
+/// A binary operator on unsigned integers.
+pub trait Op {
+    /// Returns the symbol used to display the operator.
+    fn symbol(&self) -> &str;
+
+    /// Applies the operator to `a` and `b`. Returns `None` if the result
+    /// is not representable.
+    fn apply(&self, a: usize, b: usize) -> Option<usize>;
+
+    /// Returns true if the operator implements [`Op::unapply`].
+    fn invertible(&self) -> bool {
+        false
+    }
+
+    /// Returns the left operand `a` so that `apply(a, b) == result`, or
+    /// `None` if there is no such operand.
+    fn unapply(&self, _result: usize, _b: usize) -> Option<usize> {
+        None
+    }
+
+    /// Returns true if `apply(a, b) == result` holds for any `a`, so that
+    /// there is no single operand [`Op::unapply`] could return.
+    fn absorbs(&self, _result: usize, _b: usize) -> bool {
+        false
+    }
+}
+
+/// Addition operator `+`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Add;
+
+impl Op for Add {
+    fn symbol(&self) -> &str {
+        "+"
+    }
+
+    fn apply(&self, a: usize, b: usize) -> Option<usize> {
+        a.checked_add(b)
+    }
+
+    fn invertible(&self) -> bool {
+        true
+    }
+
+    fn unapply(&self, result: usize, b: usize) -> Option<usize> {
+        result.checked_sub(b)
+    }
+}
+
+/// Multiplication operator `*`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mul;
+
+impl Op for Mul {
+    fn symbol(&self) -> &str {
+        "*"
+    }
+
+    fn apply(&self, a: usize, b: usize) -> Option<usize> {
+        a.checked_mul(b)
+    }
+
+    fn invertible(&self) -> bool {
+        true
+    }
+
+    fn unapply(&self, result: usize, b: usize) -> Option<usize> {
+        (b != 0 && result.is_multiple_of(b)).then(|| result / b)
+    }
+
+    fn absorbs(&self, result: usize, b: usize) -> bool {
+        b == 0 && result == 0
+    }
+}
+
+/// Concatenation operator `||`, which appends the decimal digits of `b` to
+/// the digits of `a`.
+///
+/// # Example
+/// ```
+/// # use lib::expr::{Concat, Op};
+/// assert_eq!(Concat.apply(12, 345), Some(12345));
+/// assert_eq!(Concat.unapply(12345, 345), Some(12));
+/// assert_eq!(Concat.unapply(12345, 44), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Concat;
+
+impl Concat {
+    /// Returns the smallest power of 10 greater than `v`.
+    fn shift(v: usize) -> Option<usize> {
+        10usize.checked_pow(v.checked_ilog10().unwrap_or_default() + 1)
+    }
+}
+
+impl Op for Concat {
+    fn symbol(&self) -> &str {
+        "||"
+    }
+
+    fn apply(&self, a: usize, b: usize) -> Option<usize> {
+        a.checked_mul(Self::shift(b)?)?.checked_add(b)
+    }
+
+    fn invertible(&self) -> bool {
+        true
+    }
+
+    fn unapply(&self, result: usize, b: usize) -> Option<usize> {
+        let shift = Self::shift(b)?;
+        (result % shift == b).then(|| result / shift)
+    }
+}
+
+/// Evaluates `operands` left-to-right by applying `ops[i]` to the result so
+/// far and `operands[i + 1]`. Returns `None` if the number of operators does
+/// not fit the number of operands or any operation fails.
+///
+/// # Example
+/// ```
+/// # use lib::expr::{evaluate, Add, Mul};
+/// assert_eq!(evaluate(&[81, 40, 27], &[&Add, &Mul]), Some(3267));
+/// ```
+pub fn evaluate(operands: &[usize], ops: &[&dyn Op]) -> Option<usize> {
+    let (&first, rest) = operands.split_first()?;
+    if rest.len() != ops.len() {
+        return None;
+    }
+
+    rest.iter()
+        .zip(ops)
+        .try_fold(first, |acc, (&b, op)| op.apply(acc, b))
+}
+
+/// Searches for a sequence of operators from `ops` which, applied
+/// left-to-right to `operands`, results in `target`.
+///
+/// If all operators are [invertible](Op::invertible), the search works
+/// backwards from the target, only following operators which can produce
+/// the current value from the last operand. This prunes most branches early.
+/// Otherwise, all combinations are tried left-to-right.
+///
+/// # Example
+/// ```
+/// # use lib::expr::{solve, Equation, Add, Mul, Concat};
+/// let ops = solve(7290, &[6, 8, 6, 15], &[&Add, &Mul, &Concat]).unwrap();
+/// let solution = Equation::new(7290, &[6, 8, 6, 15], &ops);
+/// assert_eq!(solution.to_string(), "7290 = 6 * 8 || 6 * 15");
+///
+/// assert!(solve(83, &[17, 5], &[&Add, &Mul]).is_none());
+/// ```
+pub fn solve<'a>(target: usize, operands: &[usize], ops: &[&'a dyn Op]) -> Option<Vec<&'a dyn Op>> {
+    let mut res = Vec::with_capacity(operands.len().saturating_sub(1));
+
+    // Operators are collected while the search unwinds, which is from the
+    // first operator for the backward search and from the last one for the
+    // forward search.
+    if ops.iter().all(|op| op.invertible()) {
+        solve_backward(target, operands, ops, &mut res).then_some(res)
+    } else {
+        let (&first, rest) = operands.split_first()?;
+        solve_forward(first, &|acc| acc == target, rest, ops, &mut res).then(|| {
+            res.reverse();
+            res
+        })
+    }
+}
+
+fn solve_backward<'a>(
+    target: usize,
+    operands: &[usize],
+    ops: &[&'a dyn Op],
+    res: &mut Vec<&'a dyn Op>,
+) -> bool {
+    let Some((&last, rest)) = operands.split_last() else {
+        return false;
+    };
+
+    if rest.is_empty() {
+        return last == target;
+    }
+
+    for &op in ops {
+        let found = if op.absorbs(target, last) {
+            // Any value of the left side works, like for `x * 0 = 0`, so
+            // it only has to be computable.
+            let (&first, rest) = rest.split_first().expect("rest is not empty");
+            let mut left = vec![];
+            let found = solve_forward(first, &|_| true, rest, ops, &mut left);
+            res.extend(left.into_iter().rev());
+            found
+        } else if let Some(prev) = op.unapply(target, last) {
+            solve_backward(prev, rest, ops, res)
+        } else {
+            false
+        };
+        if found {
+            res.push(op);
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Searches left-to-right for operators which give a result accepted by
+/// `done`.
+fn solve_forward<'a>(
+    acc: usize,
+    done: &dyn Fn(usize) -> bool,
+    operands: &[usize],
+    ops: &[&'a dyn Op],
+    res: &mut Vec<&'a dyn Op>,
+) -> bool {
+    let Some((&next, rest)) = operands.split_first() else {
+        return done(acc);
+    };
+
+    for &op in ops {
+        let Some(acc) = op.apply(acc, next) else {
+            continue;
+        };
+        if solve_forward(acc, done, rest, ops, res) {
+            res.push(op);
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Displays a solved equation as `target = a op b op c ...`.
+pub struct Equation<'a> {
+    target: usize,
+    operands: &'a [usize],
+    ops: &'a [&'a dyn Op],
+}
+
+impl<'a> Equation<'a> {
+    pub fn new(target: usize, operands: &'a [usize], ops: &'a [&'a dyn Op]) -> Self {
+        Self {
+            target,
+            operands,
+            ops,
+        }
+    }
+
+    /// Returns true if evaluating the equation actually results in the
+    /// target.
+    pub fn verify(&self) -> bool {
+        evaluate(self.operands, self.ops) == Some(self.target)
+    }
+}
+
+impl fmt::Display for Equation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} =", self.target)?;
+        for (i, v) in self.operands.iter().enumerate() {
+            if i > 0 {
+                write!(f, " {}", self.ops[i - 1].symbol())?;
+            }
+            write!(f, " {v}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Operator without an inverse to force the forward search.
+    struct Sub;
+
+    impl Op for Sub {
+        fn symbol(&self) -> &str {
+            "-"
+        }
+
+        fn apply(&self, a: usize, b: usize) -> Option<usize> {
+            a.checked_sub(b)
+        }
+    }
+
+    #[test]
+    fn test_concat_zero() {
+        assert_eq!(Concat.apply(5, 0), Some(50));
+        assert_eq!(Concat.unapply(50, 0), Some(5));
+        assert_eq!(Concat.unapply(55, 0), None);
+    }
+
+    #[test]
+    fn test_concat_overflow() {
+        assert_eq!(Concat.apply(usize::MAX, 1), None);
+    }
+
+    #[test]
+    fn test_forward_and_backward_agree() {
+        let cases: &[(usize, &[usize])] = &[
+            (190, &[10, 19]),
+            (3267, &[81, 40, 27]),
+            (156, &[15, 6]),
+            (192, &[17, 8, 14]),
+            (21037, &[9, 7, 18, 13]),
+            (0, &[5, 0]),
+            (0, &[7, 3, 0]),
+            (0, &[0, 0, 0]),
+            (12, &[3, 0, 4]),
+            (1, &[5, 0]),
+        ];
+
+        for &(target, operands) in cases {
+            let backward = solve(target, operands, &[&Add, &Mul, &Concat]);
+            let forward = solve(target, operands, &[&Add, &Mul, &Concat, &Sub]);
+
+            assert_eq!(backward.is_some(), forward.is_some(), "{target}");
+            if let Some(ops) = backward {
+                assert!(Equation::new(target, operands, &ops).verify());
+            }
+            if let Some(ops) = forward {
+                assert!(Equation::new(target, operands, &ops).verify());
+            }
+        }
+    }
+
+    #[test]
+    fn test_zero_operand() {
+        let ops = solve(0, &[5, 0], &[&Add, &Mul]).unwrap();
+        assert_eq!(Equation::new(0, &[5, 0], &ops).to_string(), "0 = 5 * 0");
+
+        let ops = solve(0, &[2, 3, 0], &[&Add, &Mul, &Concat]).unwrap();
+        assert!(Equation::new(0, &[2, 3, 0], &ops).verify());
+        assert!(solve(0, &[usize::MAX, 1, 0], &[&Mul, &Concat]).is_some());
+        assert!(solve(0, &[usize::MAX, 2, 0], &[&Add, &Concat]).is_none());
+    }
+
+    #[test]
+    fn test_forward_only_op() {
+        let ops = solve(5, &[10, 2, 3], &[&Add, &Sub]).unwrap();
+        assert_eq!(
+            Equation::new(5, &[10, 2, 3], &ops).to_string(),
+            "5 = 10 - 2 - 3"
+        );
+    }
+}
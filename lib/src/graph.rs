@@ -0,0 +1,286 @@
+//! Ordering of nodes by precedence rules.
+
+use core::fmt;
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet, VecDeque},
+    error::Error,
+    hash::Hash,
+};
+
+/// Error returned when nodes can not be ordered because the rules contain a
+/// cycle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError<N> {
+    /// Nodes forming the cycle in rule order, where each node must precede
+    /// the next one and the last node must precede the first one.
+    pub cycle: Vec<N>,
+}
+
+impl<N: fmt::Debug> fmt::Display for CycleError<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "rules contain a cycle: {:?}", self.cycle)
+    }
+}
+
+impl<N: fmt::Debug> Error for CycleError<N> {}
+
+/// Set of precedence rules, where each rule states that one node must be
+/// ordered before another one.
+#[derive(Debug, Clone)]
+pub struct Rules<N> {
+    after: HashMap<N, HashSet<N>>,
+}
+
+impl<N> Default for Rules<N> {
+    fn default() -> Self {
+        Self {
+            after: HashMap::new(),
+        }
+    }
+}
+
+impl<N> Rules<N>
+where
+    N: Clone + Eq + Hash,
+{
+    /// Creates an empty rule set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the rule that `before` must be ordered before `after`.
+    pub fn add(&mut self, before: N, after: N) {
+        self.after.entry(before).or_default().insert(after);
+    }
+
+    /// Returns true if there is a rule that `a` must be ordered before `b`.
+    pub fn must_precede(&self, a: &N, b: &N) -> bool {
+        self.after.get(a).is_some_and(|s| s.contains(b))
+    }
+
+    /// Returns true if no two nodes in `seq` violate any rule. Nodes which
+    /// are not part of `seq` are ignored.
+    ///
+    /// # Example
+    /// ```
+    /// # use lib::graph::Rules;
+    /// let rules: Rules<_> = [(1, 2), (2, 3)].into_iter().collect();
+    /// assert!(rules.is_ordered(&[1, 2, 3]));
+    /// assert!(rules.is_ordered(&[1, 3]));
+    /// assert!(!rules.is_ordered(&[2, 4, 1]));
+    /// ```
+    pub fn is_ordered(&self, seq: &[N]) -> bool {
+        let index: HashMap<_, _> = seq.iter().enumerate().map(|(i, n)| (n, i)).collect();
+
+        seq.iter().enumerate().all(|(i, n)| {
+            self.after
+                .get(n)
+                .into_iter()
+                .flatten()
+                .filter_map(|a| index.get(a))
+                .all(|&j| j > i)
+        })
+    }
+
+    /// Compares two nodes by the rules. Nodes without a rule between them
+    /// are considered equal.
+    ///
+    /// The result is only a valid total order for `sort_by` if the rules
+    /// between all sorted nodes are complete and free of cycles. Otherwise,
+    /// use [`Rules::toposort`].
+    ///
+    /// # Example
+    /// ```
+    /// # use lib::graph::Rules;
+    /// let rules: Rules<_> = [(3, 1), (3, 2), (1, 2)].into_iter().collect();
+    /// let mut v = vec![1, 2, 3];
+    /// v.sort_by(|a, b| rules.compare(a, b));
+    /// assert_eq!(v, vec![3, 1, 2]);
+    /// ```
+    pub fn compare(&self, a: &N, b: &N) -> Ordering {
+        if self.must_precede(a, b) {
+            Ordering::Less
+        } else if self.must_precede(b, a) {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    }
+
+    /// Orders `nodes` so that no rule is violated, only considering rules
+    /// between the given nodes. See [`toposort`].
+    ///
+    /// # Example
+    /// ```
+    /// # use lib::graph::Rules;
+    /// // The rules contain the cycle 1 -> 2 -> 3 -> 1, but as long as not
+    /// // all of its nodes are ordered, this does not matter.
+    /// let rules: Rules<_> = [(1, 2), (2, 3), (3, 1)].into_iter().collect();
+    /// assert_eq!(rules.toposort(&[3, 2]), Ok(vec![2, 3]));
+    /// assert!(rules.toposort(&[3, 2, 1]).is_err());
+    /// ```
+    pub fn toposort(&self, nodes: &[N]) -> Result<Vec<N>, CycleError<N>> {
+        let set: HashSet<_> = nodes.iter().collect();
+        toposort(nodes, |n| {
+            self.after
+                .get(n)
+                .into_iter()
+                .flatten()
+                .filter(|a| set.contains(a))
+                .cloned()
+                .collect::<Vec<_>>()
+        })
+    }
+}
+
+impl<N> FromIterator<(N, N)> for Rules<N>
+where
+    N: Clone + Eq + Hash,
+{
+    fn from_iter<T: IntoIterator<Item = (N, N)>>(iter: T) -> Self {
+        let mut rules = Self::new();
+        for (before, after) in iter {
+            rules.add(before, after);
+        }
+        rules
+    }
+}
+
+/// Orders `nodes` using Kahn's algorithm so that every node comes before all
+/// nodes returned by `after` for it. Nodes without rules between them keep
+/// their relative input order.
+///
+/// Nodes returned by `after` which are not part of `nodes` are ignored.
+/// If the rules contain a cycle, an error with the nodes forming one of the
+/// cycles is returned.
+///
+/// # Example
+/// ```
+/// # use lib::graph::toposort;
+/// let res = toposort(&['c', 'b', 'a'], |n| match n {
+///     'a' => vec!['b', 'c'],
+///     'b' => vec!['c'],
+///     _ => vec![],
+/// });
+/// assert_eq!(res, Ok(vec!['a', 'b', 'c']));
+///
+/// let err = toposort(&['a', 'b'], |n| match n {
+///     'a' => vec!['b'],
+///     _ => vec!['a'],
+/// })
+/// .unwrap_err();
+/// assert_eq!(err.cycle.len(), 2);
+/// ```
+pub fn toposort<N, I>(nodes: &[N], mut after: impl FnMut(&N) -> I) -> Result<Vec<N>, CycleError<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let index: HashMap<_, _> = nodes.iter().enumerate().map(|(i, n)| (n, i)).collect();
+
+    let mut edges = vec![vec![]; nodes.len()];
+    let mut in_degree = vec![0; nodes.len()];
+    for (i, n) in nodes.iter().enumerate() {
+        for a in after(n) {
+            if let Some(&j) = index.get(&a) {
+                edges[i].push(j);
+                in_degree[j] += 1;
+            }
+        }
+    }
+
+    let mut queue: VecDeque<_> = (0..nodes.len()).filter(|&i| in_degree[i] == 0).collect();
+    let mut res = Vec::with_capacity(nodes.len());
+
+    while let Some(i) = queue.pop_front() {
+        res.push(nodes[i].clone());
+        for &j in &edges[i] {
+            in_degree[j] -= 1;
+            if in_degree[j] == 0 {
+                queue.push_back(j);
+            }
+        }
+    }
+
+    if res.len() == nodes.len() {
+        return Ok(res);
+    }
+
+    // Every node left over has at least one left over predecessor. Walking
+    // backwards from any of them must therefore end up in a cycle.
+    let mut pred = vec![None; nodes.len()];
+    for (i, targets) in edges.iter().enumerate() {
+        for &j in targets {
+            if in_degree[i] > 0 && in_degree[j] > 0 {
+                pred[j] = Some(i);
+            }
+        }
+    }
+
+    let mut seen = vec![false; nodes.len()];
+    let mut curr = (0..nodes.len()).find(|&i| in_degree[i] > 0).unwrap();
+    while !seen[curr] {
+        seen[curr] = true;
+        curr = pred[curr].unwrap();
+    }
+
+    let start = curr;
+    let mut cycle = vec![nodes[start].clone()];
+    curr = pred[start].unwrap();
+    while curr != start {
+        cycle.push(nodes[curr].clone());
+        curr = pred[curr].unwrap();
+    }
+    cycle.reverse();
+
+    Err(CycleError { cycle })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_toposort_reports_cycle() {
+        // 0 -> 1 -> 2 -> 3 -> 1, 3 -> 4
+        let rules: Rules<_> = [(0, 1), (1, 2), (2, 3), (3, 1), (3, 4)]
+            .into_iter()
+            .collect();
+
+        let err = rules.toposort(&[4, 3, 2, 1, 0]).unwrap_err();
+        let mut cycle = err.cycle.clone();
+
+        // The cycle is reported in rule order, starting at any of its nodes.
+        let first = cycle.iter().position(|&n| n == 1).unwrap();
+        cycle.rotate_left(first);
+        assert_eq!(cycle, vec![1, 2, 3]);
+
+        for (i, n) in err.cycle.iter().enumerate() {
+            let next = err.cycle[(i + 1) % err.cycle.len()];
+            assert!(rules.must_precede(n, &next));
+        }
+    }
+
+    #[test]
+    fn test_toposort_self_cycle() {
+        let rules: Rules<_> = [(1, 1)].into_iter().collect();
+        assert_eq!(rules.toposort(&[1]), Err(CycleError { cycle: vec![1] }));
+    }
+
+    #[test]
+    fn test_toposort_keeps_unrelated_order() {
+        let rules: Rules<_> = [(5, 1)].into_iter().collect();
+        assert_eq!(rules.toposort(&[3, 1, 2, 5]), Ok(vec![3, 2, 5, 1]));
+    }
+
+    #[test]
+    fn test_is_ordered_agrees_with_toposort() {
+        let rules: Rules<_> = [(1, 2), (2, 3), (1, 3)].into_iter().collect();
+        for seq in [[1, 2, 3], [2, 1, 3], [3, 2, 1], [1, 3, 2]] {
+            let sorted = rules.toposort(&seq).unwrap();
+            assert!(rules.is_ordered(&sorted));
+            assert_eq!(rules.is_ordered(&seq), sorted == seq);
+        }
+    }
+}
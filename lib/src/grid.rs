@@ -1,4 +1,10 @@
-use crate::Pos;
+use crate::{Direction, Direction8, Pos};
+use core::fmt;
+use std::{
+    error::Error,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
 
 #[macro_export]
 macro_rules! grid {
@@ -9,6 +15,566 @@ macro_rules! grid {
     };
 }
 
+/// Error returned when a [`Grid`] can not be constructed from the given data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError {
+    /// A row has a different length than the first row of the grid.
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// The number of cells does not match `width * height`.
+    SizeMismatch { expected: usize, found: usize },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::RaggedRow {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {row} has a length of {found}, but {expected} was expected"
+            ),
+            GridError::SizeMismatch { expected, found } => {
+                write!(f, "grid expects {expected} cells, but {found} were given")
+            }
+        }
+    }
+}
+
+impl Error for GridError {}
+
+/// Two dimensional, rectangular grid of cells which are stored row-major
+/// in a flat list.
+///
+/// Cells are addressed by [`Pos`], where `x` is the column and `y` is the
+/// row, starting at the top left corner with (0, 0).
+///
+/// # Example
+/// ```
+/// # use lib::{Grid, Pos};
+/// let grid: Grid<char> = "\
+/// abc
+/// def".parse().unwrap();
+///
+/// assert_eq!(grid.width(), 3);
+/// assert_eq!(grid.height(), 2);
+/// assert_eq!(grid[Pos { x: 1, y: 1 }], 'e');
+/// assert_eq!(grid.get(Pos { x: 3, y: 0 }), None);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Creates a new grid with the given dimensions from a row-major list
+    /// of cells.
+    ///
+    /// # Example
+    /// ```
+    /// # use lib::{Grid, Pos};
+    /// let grid = Grid::new(2, 2, vec![1, 2, 3, 4]).unwrap();
+    /// assert_eq!(grid[Pos { x: 0, y: 1 }], 3);
+    ///
+    /// assert!(Grid::new(2, 2, vec![1, 2, 3]).is_err());
+    /// ```
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Result<Self, GridError> {
+        if cells.len() != width * height {
+            return Err(GridError::SizeMismatch {
+                expected: width * height,
+                found: cells.len(),
+            });
+        }
+
+        Ok(Self {
+            cells,
+            width,
+            height,
+        })
+    }
+
+    /// Creates a new grid with the given dimensions where every cell is
+    /// set to `v`.
+    ///
+    /// # Example
+    /// ```
+    /// # use lib::{Grid, Pos};
+    /// let grid = Grid::filled(3, 2, false);
+    /// assert_eq!(grid.width(), 3);
+    /// assert_eq!(grid.height(), 2);
+    /// assert!(grid.iter().all(|(_, v)| !v));
+    /// ```
+    pub fn filled(width: usize, height: usize, v: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: vec![v; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Creates a new grid from a list of rows. Returns an error if the rows
+    /// do not all have the same length.
+    ///
+    /// # Example
+    /// ```
+    /// # use lib::{Grid, Pos, grid};
+    /// let grid = Grid::from_rows(grid! {
+    ///     1, 2, 3;
+    ///     4, 5, 6;
+    /// }).unwrap();
+    /// assert_eq!(grid[Pos { x: 2, y: 1 }], 6);
+    ///
+    /// assert!(Grid::from_rows(vec![vec![1, 2], vec![3]]).is_err());
+    /// ```
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, GridError> {
+        let height = rows.len();
+        let width = rows.first().map(Vec::len).unwrap_or_default();
+
+        let mut cells = Vec::with_capacity(width * height);
+        for (y, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(GridError::RaggedRow {
+                    row: y,
+                    expected: width,
+                    found: row.len(),
+                });
+            }
+            cells.extend(row);
+        }
+
+        Ok(Self {
+            cells,
+            width,
+            height,
+        })
+    }
+
+    /// Returns the number of columns of the grid.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the number of rows of the grid.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns true if the given position is inside the bounds of the grid.
+    ///
+    /// # Example
+    /// ```
+    /// # use lib::{Grid, Pos};
+    /// let grid = Grid::filled(3, 2, 0);
+    /// assert!(grid.is_in_bounds(Pos { x: 2, y: 1 }));
+    /// assert!(!grid.is_in_bounds(Pos { x: 3, y: 1 }));
+    /// assert!(!grid.is_in_bounds(Pos { x: 0, y: -1 }));
+    /// ```
+    pub fn is_in_bounds(&self, pos: Pos) -> bool {
+        index_of(self.width, self.height, pos).is_some()
+    }
+
+    /// Returns a reference to the value at the given position, if the
+    /// position is inside the bounds of the grid.
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        index_of(self.width, self.height, pos).map(|i| &self.cells[i])
+    }
+
+    /// Returns a mutable reference to the value at the given position, if
+    /// the position is inside the bounds of the grid.
+    ///
+    /// # Example
+    /// ```
+    /// # use lib::{Grid, Pos};
+    /// let mut grid = Grid::filled(2, 2, 0);
+    /// if let Some(v) = grid.get_mut(Pos { x: 1, y: 0 }) {
+    ///     *v = 5;
+    /// }
+    /// assert_eq!(grid[Pos { x: 1, y: 0 }], 5);
+    /// assert_eq!(grid.get_mut(Pos { x: 2, y: 0 }), None);
+    /// ```
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        index_of(self.width, self.height, pos).map(|i| &mut self.cells[i])
+    }
+
+    /// Returns an iterator over all positions of the grid in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.width * self.height).map(move |i| pos_of(width, i))
+    }
+
+    /// Returns an iterator over all cells of the grid in row-major order.
+    ///
+    /// # Example
+    /// ```
+    /// # use lib::{Grid, Pos};
+    /// let grid = Grid::new(2, 2, vec![1, 2, 3, 4]).unwrap();
+    /// let cells: Vec<_> = grid.iter().collect();
+    /// assert_eq!(cells[1], (Pos { x: 1, y: 0 }, &2));
+    /// assert_eq!(cells[2], (Pos { x: 0, y: 1 }, &3));
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (pos_of(self.width, i), v))
+    }
+
+    /// Returns an iterator over mutable references to all cells of the grid
+    /// in row-major order.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Pos, &mut T)> {
+        let width = self.width;
+        self.cells
+            .iter_mut()
+            .enumerate()
+            .map(move |(i, v)| (pos_of(width, i), v))
+    }
+
+    /// Returns an iterator over all cells in the row `y`.
+    ///
+    /// # Panics
+    /// Panics if `y` is out of bounds.
+    ///
+    /// # Example
+    /// ```
+    /// # use lib::{Grid, Pos};
+    /// let grid = Grid::new(2, 2, vec![1, 2, 3, 4]).unwrap();
+    /// let row: Vec<_> = grid.row(1).map(|(_, v)| *v).collect();
+    /// assert_eq!(row, vec![3, 4]);
+    /// ```
+    pub fn row(&self, y: usize) -> impl Iterator<Item = (Pos, &T)> {
+        assert!(y < self.height, "row index out of bounds");
+        self.cells[y * self.width..(y + 1) * self.width]
+            .iter()
+            .enumerate()
+            .map(move |(x, v)| ((x as isize, y as isize).into(), v))
+    }
+
+    /// Returns an iterator over all cells in the column `x`.
+    ///
+    /// # Panics
+    /// Panics if `x` is out of bounds.
+    ///
+    /// # Example
+    /// ```
+    /// # use lib::{Grid, Pos};
+    /// let grid = Grid::new(2, 2, vec![1, 2, 3, 4]).unwrap();
+    /// let col: Vec<_> = grid.column(1).map(|(_, v)| *v).collect();
+    /// assert_eq!(col, vec![2, 4]);
+    /// ```
+    pub fn column(&self, x: usize) -> impl Iterator<Item = (Pos, &T)> {
+        assert!(x < self.width, "column index out of bounds");
+        self.cells
+            .iter()
+            .skip(x)
+            .step_by(self.width)
+            .enumerate()
+            .map(move |(y, v)| ((x as isize, y as isize).into(), v))
+    }
+
+    /// Returns an iterator over the rows of the grid as slices.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks panics on a chunk size of 0, so an empty grid must be
+        // handled separately.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Returns an iterator over the in-bounds neighbours of `pos` in the four
+    /// orthogonal directions.
+    ///
+    /// # Example
+    /// ```
+    /// # use lib::{Grid, Pos, Direction};
+    /// let grid = Grid::new(3, 2, vec![1, 2, 3, 4, 5, 6]).unwrap();
+    /// let n: Vec<_> = grid
+    ///     .neighbours(Pos { x: 0, y: 0 })
+    ///     .map(|n| (n.dir, *n.value))
+    ///     .collect();
+    /// assert_eq!(n, vec![(Direction::Right, 2), (Direction::Down, 4)]);
+    /// ```
+    pub fn neighbours(&self, pos: Pos) -> Neighbours<'_, T, Direction, 4> {
+        Neighbours::new(self, pos, Direction::all())
+    }
+
+    /// Returns an iterator over the in-bounds neighbours of `pos` in the four
+    /// diagonal directions.
+    ///
+    /// # Example
+    /// ```
+    /// # use lib::{Grid, Pos, Direction8};
+    /// let grid = Grid::new(3, 2, vec![1, 2, 3, 4, 5, 6]).unwrap();
+    /// let n: Vec<_> = grid
+    ///     .diagonal_neighbours(Pos { x: 1, y: 1 })
+    ///     .map(|n| (n.dir, *n.value))
+    ///     .collect();
+    /// assert_eq!(n, vec![(Direction8::UpRight, 3), (Direction8::UpLeft, 1)]);
+    /// ```
+    pub fn diagonal_neighbours(&self, pos: Pos) -> Neighbours<'_, T, Direction8, 4> {
+        Neighbours::new(self, pos, Direction8::diagonals())
+    }
+
+    /// Returns an iterator over the in-bounds neighbours of `pos` in all
+    /// eight directions, including diagonals.
+    ///
+    /// # Example
+    /// ```
+    /// # use lib::{Grid, Pos};
+    /// let grid = Grid::new(3, 2, vec![1, 2, 3, 4, 5, 6]).unwrap();
+    /// assert_eq!(grid.all_neighbours(Pos { x: 1, y: 1 }).count(), 5);
+    /// assert_eq!(grid.all_neighbours(Pos { x: 0, y: 0 }).count(), 3);
+    /// ```
+    pub fn all_neighbours(&self, pos: Pos) -> Neighbours<'_, T, Direction8, 8> {
+        Neighbours::new(self, pos, Direction8::all())
+    }
+
+    /// Tries to find the first occurence of `v` in the grid.
+    ///
+    /// # Example
+    /// ```
+    /// # use lib::{Grid, Pos};
+    /// let grid = Grid::new(3, 2, vec![1, 2, 3, 4, 5, 6]).unwrap();
+    /// assert_eq!(grid.find(&5), Some(Pos { x: 1, y: 1 }));
+    /// assert_eq!(grid.find(&7), None);
+    /// ```
+    pub fn find(&self, v: &T) -> Option<Pos>
+    where
+        T: PartialEq,
+    {
+        self.iter().find(|(_, e)| *e == v).map(|(p, _)| p)
+    }
+
+    /// Tries to find all occurences of `v` in the grid and returns the
+    /// found positions in row-major order.
+    pub fn find_all(&self, v: &T) -> Vec<Pos>
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(|(_, e)| *e == v)
+            .map(|(p, _)| p)
+            .collect()
+    }
+
+    /// Creates a new grid with the same dimensions by applying `f` to
+    /// every cell.
+    ///
+    /// # Example
+    /// ```
+    /// # use lib::{Grid, Pos};
+    /// let grid: Grid<char> = "12\n34".parse().unwrap();
+    /// let grid = grid.map(|c| c.to_digit(10).unwrap());
+    /// assert_eq!(grid[Pos { x: 1, y: 1 }], 4);
+    /// ```
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Returns a new grid with rows flipped to columns and columns flipped
+    /// to rows.
+    ///
+    /// # Example
+    /// ```
+    /// # use lib::{Grid, Pos};
+    /// let grid = Grid::new(3, 2, vec![1, 2, 3, 4, 5, 6]).unwrap();
+    /// let flipped = grid.transpose();
+    /// assert_eq!(flipped.width(), 2);
+    /// assert_eq!(flipped.height(), 3);
+    /// assert_eq!(flipped[Pos { x: 1, y: 2 }], 6);
+    /// ```
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .flat_map(|x| self.column(x).map(|(_, v)| v.clone()))
+            .collect();
+
+        Self {
+            cells,
+            width: self.height,
+            height: self.width,
+        }
+    }
+
+    /// Converts the grid into a list of rows.
+    pub fn into_rows(self) -> Vec<Vec<T>>
+    where
+        T: Clone,
+    {
+        self.rows().map(|r| r.to_vec()).collect()
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("position {pos} is out of grid bounds"))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("position {pos} is out of grid bounds"))
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = GridError;
+
+    /// Parses a grid using each line as row and each character in a line
+    /// as column. All lines must have the same length.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_rows(to_grid(s))
+    }
+}
+
+impl<T> TryFrom<Vec<Vec<T>>> for Grid<T> {
+    type Error = GridError;
+
+    fn try_from(rows: Vec<Vec<T>>) -> Result<Self, Self::Error> {
+        Self::from_rows(rows)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y != 0 {
+                writeln!(f)?;
+            }
+            for v in row {
+                write!(f, "{v}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A neighbouring cell of a position in a [`Grid`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Neighbour<'a, T, D> {
+    /// Position of the neighbouring cell.
+    pub pos: Pos,
+    /// Value of the neighbouring cell.
+    pub value: &'a T,
+    /// Direction from the origin position to the neighbouring cell.
+    pub dir: D,
+}
+
+/// Iterator over the in-bounds neighbours of a position in a [`Grid`].
+///
+/// Created by [`Grid::neighbours`], [`Grid::diagonal_neighbours`] and
+/// [`Grid::all_neighbours`].
+pub struct Neighbours<'a, T, D, const N: usize> {
+    grid: &'a Grid<T>,
+    pos: Pos,
+    dirs: std::array::IntoIter<D, N>,
+}
+
+impl<'a, T, D, const N: usize> Neighbours<'a, T, D, N>
+where
+    D: Copy + Into<Pos>,
+{
+    fn new(grid: &'a Grid<T>, pos: Pos, dirs: [D; N]) -> Self {
+        Self {
+            grid,
+            pos,
+            dirs: dirs.into_iter(),
+        }
+    }
+
+    /// Only yields neighbours which values are equal to `v`.
+    ///
+    /// # Example
+    /// ```
+    /// # use lib::{Grid, Pos};
+    /// let grid: Grid<char> = "XMX\nMAM\nSXS".parse().unwrap();
+    /// let n: Vec<_> = grid
+    ///     .all_neighbours(Pos { x: 1, y: 1 })
+    ///     .equal_to(&'M')
+    ///     .map(|n| n.pos)
+    ///     .collect();
+    /// assert_eq!(n, vec![
+    ///     Pos { x: 1, y: 0 },
+    ///     Pos { x: 2, y: 1 },
+    ///     Pos { x: 0, y: 1 },
+    /// ]);
+    /// ```
+    pub fn equal_to(self, v: &'a T) -> impl Iterator<Item = Neighbour<'a, T, D>>
+    where
+        T: PartialEq,
+    {
+        self.filter(move |n| n.value == v)
+    }
+
+    /// Only yields neighbours which values match the given predicate.
+    ///
+    /// # Example
+    /// ```
+    /// # use lib::{Grid, Pos};
+    /// let grid = Grid::new(3, 1, vec![1, 2, 3]).unwrap();
+    /// let n: Vec<_> = grid
+    ///     .neighbours(Pos { x: 1, y: 0 })
+    ///     .matching(|v| *v > 2)
+    ///     .map(|n| n.pos)
+    ///     .collect();
+    /// assert_eq!(n, vec![Pos { x: 2, y: 0 }]);
+    /// ```
+    pub fn matching<P>(self, mut pred: P) -> impl Iterator<Item = Neighbour<'a, T, D>>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.filter(move |n| pred(n.value))
+    }
+}
+
+impl<'a, T, D, const N: usize> Iterator for Neighbours<'a, T, D, N>
+where
+    D: Copy + Into<Pos>,
+{
+    type Item = Neighbour<'a, T, D>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for dir in self.dirs.by_ref() {
+            let pos = self.pos + dir.into();
+            if let Some(value) = self.grid.get(pos) {
+                return Some(Neighbour { pos, value, dir });
+            }
+        }
+        None
+    }
+}
+
+/// Returns the flat index of `pos` in a row-major grid with the given
+/// dimensions, if `pos` is inside the bounds.
+fn index_of(width: usize, height: usize, pos: Pos) -> Option<usize> {
+    if pos.is_any_negative() || pos.x as usize >= width || pos.y as usize >= height {
+        None
+    } else {
+        Some(pos.y as usize * width + pos.x as usize)
+    }
+}
+
+fn pos_of(width: usize, i: usize) -> Pos {
+    ((i % width) as isize, (i / width) as isize).into()
+}
+
 /// Takes a string input and transforms it into a grid by using each
 /// line as row and character in line as column.
 ///
@@ -43,14 +609,7 @@ pub fn to_grid(input: &str) -> Vec<Vec<char>> {
 /// assert_eq!(find(&grid, &7), None);
 /// ```
 pub fn find<T: PartialEq>(grid: &[Vec<T>], v: &T) -> Option<Pos> {
-    for (y, row) in grid.iter().enumerate() {
-        for (x, e) in row.iter().enumerate() {
-            if e == v {
-                return Some((x as isize, y as isize).into());
-            }
-        }
-    }
-    None
+    positions_of(grid, v).next()
 }
 
 /// Tries to find all occurences of `v` in the `grid` and returns the
@@ -71,15 +630,17 @@ pub fn find<T: PartialEq>(grid: &[Vec<T>], v: &T) -> Option<Pos> {
 /// assert!(find_all(&grid, &7).is_empty());
 /// ```
 pub fn find_all<T: PartialEq>(grid: &[Vec<T>], v: &T) -> Vec<Pos> {
-    let mut res = vec![];
-    for (y, row) in grid.iter().enumerate() {
-        for (x, e) in row.iter().enumerate() {
-            if e == v {
-                res.push((x as isize, y as isize).into())
-            }
-        }
-    }
-    res
+    positions_of(grid, v).collect()
+}
+
+/// Returns the positions of all cells equal to `v` in row-major order.
+fn positions_of<'a, T: PartialEq>(grid: &'a [Vec<T>], v: &'a T) -> impl Iterator<Item = Pos> + 'a {
+    grid.iter().enumerate().flat_map(move |(y, row)| {
+        row.iter()
+            .enumerate()
+            .filter(move |(_, e)| *e == v)
+            .map(move |(x, _)| (x as isize, y as isize).into())
+    })
 }
 
 /// Returns true if the given position is inside the coordinate bounds of
@@ -102,7 +663,8 @@ pub fn find_all<T: PartialEq>(grid: &[Vec<T>], v: &T) -> Vec<Pos> {
 /// assert!(!is_in_bounds(&grid, Pos { x: 0, y: -1 }));
 /// ```
 pub fn is_in_bounds<T>(grid: &[Vec<T>], pos: Pos) -> bool {
-    !pos.is_any_negative() && pos.y < grid.len() as isize && pos.x < grid[0].len() as isize
+    let width = grid.first().map(Vec::len).unwrap_or_default();
+    index_of(width, grid.len(), pos).is_some()
 }
 
 /// Returns the value at the given position in the grid, if the
@@ -121,11 +683,10 @@ pub fn is_in_bounds<T>(grid: &[Vec<T>], pos: Pos) -> bool {
 /// assert_eq!(get_at(&grid, Pos { x: -1, y: 0 }), None);
 /// ```
 pub fn get_at<T>(grid: &[Vec<T>], pos: Pos) -> Option<&T> {
-    if !is_in_bounds(grid, pos) {
-        None
-    } else {
-        Some(&grid[pos.y as usize][pos.x as usize])
+    if pos.is_any_negative() {
+        return None;
     }
+    grid.get(pos.y as usize)?.get(pos.x as usize)
 }
 
 /// Takes a matrix flips rows to colums and colums to rows.
@@ -183,4 +744,115 @@ mod test {
     fn test_flip_grid_panic_row_length_missmatch() {
         flip_grid(&[vec![1, 2], vec![3]]);
     }
+
+    #[test]
+    fn test_ragged_rows() {
+        let grid = vec![vec![1, 2, 3], vec![4], vec![5, 3]];
+        assert_eq!(find(&grid, &3), Some(Pos { x: 2, y: 0 }));
+        assert_eq!(
+            find_all(&grid, &3),
+            vec![Pos { x: 2, y: 0 }, Pos { x: 1, y: 2 }]
+        );
+        assert_eq!(get_at(&grid, Pos { x: 0, y: 1 }), Some(&4));
+        assert_eq!(get_at(&grid, Pos { x: 1, y: 1 }), None);
+        assert_eq!(get_at(&grid, Pos { x: 1, y: 2 }), Some(&3));
+    }
+
+    #[test]
+    fn test_grid_from_str() {
+        let grid: Grid<char> = "ab\ncd\n".parse().unwrap();
+        assert_eq!(grid.width(), 2);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid.to_string(), "ab\ncd");
+    }
+
+    #[test]
+    fn test_grid_from_str_ragged() {
+        assert_eq!(
+            "abc\nd\nefg".parse::<Grid<char>>(),
+            Err(GridError::RaggedRow {
+                row: 1,
+                expected: 3,
+                found: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_grid_empty() {
+        let grid: Grid<char> = "".parse().unwrap();
+        assert_eq!(grid.width(), 0);
+        assert_eq!(grid.height(), 0);
+        assert_eq!(grid.iter().count(), 0);
+        assert_eq!(grid.rows().count(), 0);
+        assert!(!grid.is_in_bounds(Pos { x: 0, y: 0 }));
+    }
+
+    #[test]
+    fn test_grid_index_mut() {
+        let mut grid = Grid::filled(2, 3, 0);
+        grid[Pos { x: 1, y: 2 }] = 7;
+        assert_eq!(grid.find_all(&7), vec![Pos { x: 1, y: 2 }]);
+        assert_eq!(grid.into_rows(), vec![vec![0, 0], vec![0, 0], vec![0, 7]]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_grid_index_out_of_bounds() {
+        let grid = Grid::filled(2, 2, 0);
+        let _ = grid[Pos { x: 2, y: 0 }];
+    }
+
+    /// Returns the grid with the digits 1 to 9 in three rows.
+    fn digits() -> Grid<u8> {
+        Grid::new(3, 3, (1..=9).collect()).unwrap()
+    }
+
+    fn values<'a, D>(neighbours: impl Iterator<Item = Neighbour<'a, u8, D>>) -> Vec<u8> {
+        neighbours.map(|n| *n.value).collect()
+    }
+
+    #[test]
+    fn test_neighbours_at_corners() {
+        let grid = &digits();
+
+        let top_left = Pos { x: 0, y: 0 };
+        assert_eq!(values(grid.neighbours(top_left)), [2, 4]);
+        assert_eq!(values(grid.diagonal_neighbours(top_left)), [5]);
+        assert_eq!(values(grid.all_neighbours(top_left)), [2, 5, 4]);
+
+        let bottom_right = Pos { x: 2, y: 2 };
+        assert_eq!(values(grid.neighbours(bottom_right)), [6, 8]);
+        assert_eq!(values(grid.diagonal_neighbours(bottom_right)), [5]);
+        assert_eq!(values(grid.all_neighbours(bottom_right)), [6, 8, 5]);
+
+        let top_right = Pos { x: 2, y: 0 };
+        assert_eq!(values(grid.diagonal_neighbours(top_right)), [5]);
+        let bottom_left = Pos { x: 0, y: 2 };
+        assert_eq!(values(grid.diagonal_neighbours(bottom_left)), [5]);
+    }
+
+    #[test]
+    fn test_neighbours_at_edges() {
+        let grid = &digits();
+
+        let top = Pos { x: 1, y: 0 };
+        assert_eq!(values(grid.neighbours(top)), [3, 5, 1]);
+        assert_eq!(values(grid.diagonal_neighbours(top)), [6, 4]);
+        assert_eq!(values(grid.all_neighbours(top)), [3, 6, 5, 4, 1]);
+
+        let left = Pos { x: 0, y: 1 };
+        assert_eq!(values(grid.neighbours(left)), [1, 5, 7]);
+        assert_eq!(values(grid.diagonal_neighbours(left)), [2, 8]);
+        assert_eq!(values(grid.all_neighbours(left)), [1, 2, 5, 8, 7]);
+
+        let centre = Pos { x: 1, y: 1 };
+        assert_eq!(
+            values(grid.all_neighbours(centre)),
+            [2, 3, 6, 9, 8, 7, 4, 1]
+        );
+
+        let single = Grid::new(1, 1, vec![0]).unwrap();
+        assert_eq!(single.all_neighbours(Pos { x: 0, y: 0 }).count(), 0);
+    }
 }
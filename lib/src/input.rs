@@ -0,0 +1,204 @@
+use crate::Part;
+use core::fmt;
+use std::{
+    error::Error,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+/// Error returned when the puzzle input can not be loaded.
+#[derive(Debug)]
+pub enum InputError {
+    /// The input file could not be read.
+    File { path: PathBuf, source: io::Error },
+    /// The input could not be read from stdin.
+    Stdin(io::Error),
+    /// The command line arguments selecting the input are invalid.
+    Args(String),
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::File { path, source } => {
+                write!(f, "failed reading input file {}: {source}", path.display())
+            }
+            Self::Stdin(source) => write!(f, "failed reading input from stdin: {source}"),
+            Self::Args(msg) => write!(f, "{msg}"),
+        }
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::File { source, .. } | Self::Stdin(source) => Some(source),
+            Self::Args(_) => None,
+        }
+    }
+}
+
+/// Selects which input a day's solution is run with.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The personal puzzle input `input.txt` in the day's directory.
+    #[default]
+    Puzzle,
+    /// The example input of the puzzle. For part `N`, this is
+    /// `test_input_N.txt` in the day's directory if it exists and
+    /// `test_input.txt` otherwise.
+    Example,
+    /// An explicitly given file.
+    Path(PathBuf),
+    /// Input piped via stdin.
+    Stdin,
+}
+
+impl InputSource {
+    /// Picks the input source from command line arguments. `--test` selects
+    /// the example input and `--input <path>` an explicit file, where `-`
+    /// reads from stdin. All other arguments are ignored.
+    ///
+    /// # Example
+    /// ```
+    /// # use lib::InputSource;
+    /// let args = |a: &[&str]| InputSource::from_args(a.iter().map(|s| s.to_string()));
+    ///
+    /// assert_eq!(args(&[]).unwrap(), InputSource::Puzzle);
+    /// assert_eq!(args(&["3", "--test"]).unwrap(), InputSource::Example);
+    /// assert_eq!(args(&["--input", "-"]).unwrap(), InputSource::Stdin);
+    /// assert!(args(&["--input"]).is_err());
+    /// ```
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<Self, InputError> {
+        let mut res = Self::Puzzle;
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--test" if res == Self::Puzzle => res = Self::Example,
+                "--input" => {
+                    res = match args.next().as_deref() {
+                        Some("-") => Self::Stdin,
+                        Some(path) => Self::Path(path.into()),
+                        None => return Err(InputError::Args("--input requires a path".into())),
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(res)
+    }
+
+    /// Returns the key under which the expected answers for this input are
+    /// stored in a day's [answers](crate::Answers), if there is one.
+    pub fn answers_key(&self) -> Option<&'static str> {
+        match self {
+            Self::Puzzle => Some("input"),
+            Self::Example => Some("example"),
+            Self::Path(_) | Self::Stdin => None,
+        }
+    }
+
+    /// Returns where the input for `part` of the day in `dir` is read from.
+    pub fn locate(&self, dir: impl AsRef<Path>, part: Part) -> InputLocation {
+        let dir = dir.as_ref();
+        match self {
+            Self::Puzzle => InputLocation::File(dir.join("input.txt")),
+            Self::Example => {
+                let numbered = dir.join(format!("test_input_{part}.txt"));
+                if numbered.is_file() {
+                    InputLocation::File(numbered)
+                } else {
+                    InputLocation::File(dir.join("test_input.txt"))
+                }
+            }
+            Self::Path(path) => InputLocation::File(path.clone()),
+            Self::Stdin => InputLocation::Stdin,
+        }
+    }
+}
+
+/// Concrete location of an input, see [`InputSource::locate`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum InputLocation {
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputLocation {
+    /// Reads the input and returns its contents with `\r\n` line endings
+    /// normalised to `\n` and trailing whitespace removed.
+    pub fn read(&self) -> Result<String, InputError> {
+        let mut input = String::new();
+        match self {
+            Self::File(path) => {
+                input = std::fs::read_to_string(path).map_err(|source| InputError::File {
+                    path: path.clone(),
+                    source,
+                })?;
+            }
+            Self::Stdin => {
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(InputError::Stdin)?;
+            }
+        }
+
+        if input.contains("\r\n") {
+            input = input.replace("\r\n", "\n");
+        }
+        input.truncate(input.trim_end().len());
+        Ok(input)
+    }
+}
+
+impl fmt::Display for InputLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::File(path) => write!(f, "{}", path.display()),
+            Self::Stdin => write!(f, "<stdin>"),
+        }
+    }
+}
+
+/// Reads the input of the day in `dir` for `part` from `source`.
+pub fn read_input(
+    dir: impl AsRef<Path>,
+    source: &InputSource,
+    part: Part,
+) -> Result<String, InputError> {
+    source.locate(dir, part).read()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_numbered_examples() {
+        let dir = std::env::temp_dir().join(format!("lib-input-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("test_input.txt"), "first\n\n").unwrap();
+
+        let read = |part| read_input(&dir, &InputSource::Example, part).unwrap();
+        assert_eq!(read(Part::One), "first");
+        assert_eq!(read(Part::Two), "first");
+
+        fs::write(dir.join("test_input_2.txt"), "second\r\nline\r\n").unwrap();
+        assert_eq!(read(Part::One), "first");
+        assert_eq!(read(Part::Two), "second\nline");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_missing_file_names_path() {
+        let err = read_input("/does/not/exist", &InputSource::Puzzle, Part::One).unwrap_err();
+        assert!(
+            err.to_string().contains("/does/not/exist/input.txt"),
+            "{err}"
+        );
+    }
+}
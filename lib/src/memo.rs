@@ -0,0 +1,163 @@
+//! Memoization of recursive functions.
+
+use std::{collections::HashMap, hash::Hash};
+
+/// Cache of computed function results by their arguments.
+///
+/// # Example
+/// ```
+/// # use lib::memo::Memo;
+/// fn fib(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+///     memo.get_or_compute(n, |memo| match n {
+///         0 | 1 => n,
+///         _ => fib(memo, n - 1) + fib(memo, n - 2),
+///     })
+/// }
+///
+/// let mut memo = Memo::new();
+/// assert_eq!(fib(&mut memo, 80), 23416728348467685);
+/// assert_eq!(memo.len(), 81);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self {
+            cache: HashMap::new(),
+        }
+    }
+}
+
+impl<K, V> Memo<K, V>
+where
+    K: Eq + Hash,
+    V: Clone,
+{
+    /// Creates an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached value for `key`. If there is none, `compute` is
+    /// called with the cache, so that it can recurse, and its result is
+    /// cached before it is returned.
+    pub fn get_or_compute(&mut self, key: K, compute: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(v) = self.cache.get(&key) {
+            return v.clone();
+        }
+
+        let v = compute(self);
+        self.cache.insert(key, v.clone());
+        v
+    }
+
+    /// Returns the cached value for `key`, if any.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    /// Returns the number of cached values.
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Returns true if no value has been cached yet.
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Removes all cached values.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+}
+
+/// A memoized recursive function created by [`memoize`].
+pub struct Memoized<K, V, F> {
+    memo: Memo<K, V>,
+    f: F,
+}
+
+impl<K, V, F> Memoized<K, V, F>
+where
+    K: Clone + Eq + Hash,
+    V: Clone,
+    F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+{
+    /// Calls the function with `key`, returning the cached result if it
+    /// has been called with the same key before.
+    pub fn call(&mut self, key: K) -> V {
+        fn call<K, V, F>(memo: &mut Memo<K, V>, f: &F, key: K) -> V
+        where
+            K: Clone + Eq + Hash,
+            V: Clone,
+            F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+        {
+            memo.get_or_compute(key.clone(), |memo| f(&mut |k| call(memo, f, k), key))
+        }
+
+        call(&mut self.memo, &self.f, key)
+    }
+
+    /// Returns the underlying cache.
+    pub fn memo(&self) -> &Memo<K, V> {
+        &self.memo
+    }
+}
+
+/// Wraps a recursive function so that the result for every argument is
+/// only computed once. Instead of calling itself directly, `f` receives a
+/// function to recurse with as first argument.
+///
+/// # Example
+/// ```
+/// # use lib::memo::memoize;
+/// let mut fib = memoize(|fib, n: u64| match n {
+///     0 | 1 => n,
+///     _ => fib(n - 1) + fib(n - 2),
+/// });
+///
+/// assert_eq!(fib.call(80), 23416728348467685);
+/// ```
+pub fn memoize<K, V, F>(f: F) -> Memoized<K, V, F>
+where
+    K: Clone + Eq + Hash,
+    V: Clone,
+    F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+{
+    Memoized {
+        memo: Memo::new(),
+        f,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_every_path_is_cached() {
+        let calls = Cell::new(0);
+        let mut collatz = memoize(|collatz, n: u64| {
+            calls.set(calls.get() + 1);
+            match n {
+                1 => 0,
+                _ if n.is_multiple_of(2) => 1 + collatz(n / 2),
+                _ => 1 + collatz(3 * n + 1),
+            }
+        });
+
+        assert_eq!(collatz.call(27), 111);
+        assert_eq!(calls.get(), 112);
+        assert_eq!(collatz.memo().len(), 112);
+
+        // Every value on the way has been cached, including the base case.
+        assert_eq!(collatz.call(1), 0);
+        assert_eq!(collatz.call(82), 110);
+        assert_eq!(calls.get(), 112);
+    }
+}
@@ -0,0 +1,574 @@
+//! Helpers extracting integers from puzzle inputs.
+//!
+//! Integers are runs of ASCII digits, everything else separates them. A `-`
+//! directly in front of the digits belongs to the integer, so that it fails
+//! to parse as an unsigned type instead of silently losing its sign. Only
+//! right after a digit, like in the range `3-7`, a `-` is a separator.
+//!
+//! # Example
+//! ```
+//! use lib::parse::*;
+//!
+//! let [a, b, c] = ints::<3, i32>("move -3 from 1 to 7").unwrap();
+//! assert_eq!([a, b, c], [-3, 1, 7]);
+//! assert_eq!(
+//!     ints::<3, i32>("1 2").unwrap_err().to_string(),
+//!     "line 1: expected 3 integers, found 2"
+//! );
+//!
+//! let [from, to]: [u8; 2] = ints("range 3-7").unwrap();
+//! assert_eq!((from, to), (3, 7));
+//! assert_eq!(
+//!     ints::<2, u32>("3 -4").unwrap_err().to_string(),
+//!     "line 1: invalid integer '-4'"
+//! );
+//!
+//! let [left, right] = columns::<2, i64>("3   4\n4   -3\n").unwrap();
+//! assert_eq!(left, [3, 4]);
+//! assert_eq!(right, [4, -3]);
+//! ```
+//!
+//! Inputs made of several blocks separated by blank lines are split with
+//! [`sections`], which returns a [`Section`] per block offering the same
+//! helpers with line numbers relative to the whole input. Solutions can
+//! also ask for a tuple with one element per section from
+//! [`typed_sections`], like `(Vec<Rule>, Vec<Vec<usize>>)` for a section of
+//! [records](crate::record) and a section of integer lists.
+
+use crate::record::{self, Record, RecordError};
+use core::fmt;
+use std::{borrow::Cow, error::Error, ops::Range, str::FromStr};
+
+/// Error returned when the integers of an input can not be extracted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IntError {
+    /// The token is no valid integer of the requested type, for example
+    /// because it is out of range.
+    Invalid { line: usize, token: String },
+    /// The line does not contain the expected number of integers.
+    Count {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl IntError {
+    /// Returns the 1-based number of the line the error occurred in.
+    pub fn line(&self) -> usize {
+        match self {
+            Self::Invalid { line, .. } | Self::Count { line, .. } => *line,
+        }
+    }
+}
+
+impl fmt::Display for IntError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid { line, token } => write!(f, "line {line}: invalid integer '{token}'"),
+            Self::Count {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {line}: expected {expected} integers, found {found}"
+            ),
+        }
+    }
+}
+
+impl Error for IntError {}
+
+/// An integer type which can be extracted from text.
+pub trait Int: FromStr {}
+
+macro_rules! impl_int {
+    ($($t: ty),+) => {
+        $(impl Int for $t {})+
+    };
+}
+
+impl_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// Returns the integer tokens in `s` with the 1-based numbers of their
+/// lines.
+fn tokens(s: &str) -> impl Iterator<Item = (usize, &str)> {
+    let bytes = s.as_bytes();
+    let mut line = 1;
+    let mut i = 0;
+
+    std::iter::from_fn(move || {
+        while i < bytes.len() {
+            let start = i;
+            // A `-` following a digit separates a range like `3-7`.
+            let signed = bytes[i] == b'-'
+                && bytes.get(i + 1).is_some_and(u8::is_ascii_digit)
+                && !(i > 0 && bytes[i - 1].is_ascii_digit());
+            if bytes[i].is_ascii_digit() || signed {
+                i += 1;
+                while i < bytes.len() && bytes[i].is_ascii_digit() {
+                    i += 1;
+                }
+                return Some((line, &s[start..i]));
+            }
+            if bytes[i] == b'\n' {
+                line += 1;
+            }
+            i += 1;
+        }
+        None
+    })
+}
+
+/// Returns all integers in `s`, which may span multiple lines.
+///
+/// # Example
+/// ```
+/// # use lib::parse::{IntError, all_ints};
+/// assert_eq!(all_ints::<i32>("a=-1, b=2\nc=3"), Ok(vec![-1, 2, 3]));
+/// assert_eq!(
+///     all_ints::<u8>("1 2\n3 256"),
+///     Err(IntError::Invalid { line: 2, token: "256".into() })
+/// );
+/// ```
+pub fn all_ints<T: Int>(s: &str) -> Result<Vec<T>, IntError> {
+    tokens(s)
+        .map(|(line, token)| {
+            token.parse().map_err(|_| IntError::Invalid {
+                line,
+                token: token.to_owned(),
+            })
+        })
+        .collect()
+}
+
+/// Returns exactly `N` integers of a single line.
+pub fn ints<const N: usize, T: Int>(line: &str) -> Result<[T; N], IntError> {
+    let ints = all_ints(line)?;
+    let found = ints.len();
+    ints.try_into().map_err(|_| IntError::Count {
+        line: 1,
+        expected: N,
+        found,
+    })
+}
+
+/// Returns the integers of each line of `input`.
+///
+/// # Example
+/// ```
+/// # use lib::parse::int_lines;
+/// assert_eq!(
+///     int_lines::<u32>("7 6 4\n\n1,2").unwrap(),
+///     [vec![7, 6, 4], vec![], vec![1, 2]]
+/// );
+/// ```
+pub fn int_lines<T: Int>(input: &str) -> Result<Vec<Vec<T>>, IntError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, l)| all_ints(l).map_err(|e| at_line(e, i + 1)))
+        .collect()
+}
+
+/// Returns exactly `N` integers of each line of `input`, skipping blank
+/// lines.
+///
+/// # Example
+/// ```
+/// # use lib::parse::{IntError, fixed_lines};
+/// assert_eq!(fixed_lines::<2, u32>("47|53\n97|13\n").unwrap(), [[47, 53], [97, 13]]);
+/// assert_eq!(
+///     fixed_lines::<2, u32>("47|53\n\n97").unwrap_err(),
+///     IntError::Count { line: 3, expected: 2, found: 1 }
+/// );
+/// ```
+pub fn fixed_lines<const N: usize, T: Int>(input: &str) -> Result<Vec<[T; N]>, IntError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty())
+        .map(|(i, l)| ints(l).map_err(|e| at_line(e, i + 1)))
+        .collect()
+}
+
+/// Reads an input of `N` integer columns, skipping blank lines, and returns
+/// the values of each column.
+pub fn columns<const N: usize, T: Int>(input: &str) -> Result<[Vec<T>; N], IntError> {
+    let mut columns = std::array::from_fn(|_| vec![]);
+    for row in fixed_lines::<N, T>(input)? {
+        for (column, value) in columns.iter_mut().zip(row) {
+            column.push(value);
+        }
+    }
+    Ok(columns)
+}
+
+/// Moves an error of a single line to line `line` of the whole input.
+fn at_line(err: IntError, line: usize) -> IntError {
+    match err {
+        IntError::Invalid { token, .. } => IntError::Invalid { line, token },
+        IntError::Count {
+            expected, found, ..
+        } => IntError::Count {
+            line,
+            expected,
+            found,
+        },
+    }
+}
+
+/// Error returned when an input can not be split into the expected
+/// sections.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SectionError {
+    /// The input does not consist of the expected number of sections.
+    Count { expected: usize, found: usize },
+    /// A section does not match the pattern of its record type.
+    Record(RecordError),
+    /// A line of a section does not contain valid integers.
+    Int(IntError),
+}
+
+impl SectionError {
+    /// Moves an error reported for line 1 of a part of the input to the
+    /// given line.
+    fn at_line(self, line: usize) -> Self {
+        match self {
+            Self::Count { .. } => self,
+            Self::Record(err) => Self::Record(RecordError {
+                line: err.line + line - 1,
+                ..err
+            }),
+            Self::Int(err) => {
+                let err_line = err.line();
+                Self::Int(at_line(err, err_line + line - 1))
+            }
+        }
+    }
+}
+
+impl fmt::Display for SectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Count { expected, found } => write!(
+                f,
+                "expected {expected} sections separated by blank lines, found {found}"
+            ),
+            Self::Record(err) => write!(f, "{err}"),
+            Self::Int(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl Error for SectionError {}
+
+/// A block of an input split by [`sections`].
+///
+/// The parsing helpers report errors with the line numbers in the whole
+/// input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section<'a> {
+    text: Cow<'a, str>,
+    line: usize,
+}
+
+impl Section<'_> {
+    /// Returns the text of the section without the separating blank lines.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the 1-based number of the first line of the section in the
+    /// input.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns all integers in the section, see [`all_ints`].
+    pub fn ints<T: Int>(&self) -> Result<Vec<T>, IntError> {
+        all_ints(&self.text).map_err(|e| self.int_error(e))
+    }
+
+    /// Returns the integers of each line, see [`int_lines`].
+    pub fn int_lines<T: Int>(&self) -> Result<Vec<Vec<T>>, IntError> {
+        int_lines(&self.text).map_err(|e| self.int_error(e))
+    }
+
+    /// Returns exactly `N` integers of each line, see [`fixed_lines`].
+    pub fn fixed_lines<const N: usize, T: Int>(&self) -> Result<Vec<[T; N]>, IntError> {
+        fixed_lines(&self.text).map_err(|e| self.int_error(e))
+    }
+
+    /// Returns the values of `N` integer columns, see [`columns`].
+    pub fn columns<const N: usize, T: Int>(&self) -> Result<[Vec<T>; N], IntError> {
+        columns(&self.text).map_err(|e| self.int_error(e))
+    }
+
+    /// Parses each line as a record, see [`record::records`].
+    pub fn records<T: FromStr<Err = RecordError>>(&self) -> Result<Vec<T>, RecordError> {
+        record::records(&self.text).map_err(|e| self.record_error(e))
+    }
+
+    /// Parses the whole section as a single record.
+    pub fn parse<T: FromStr<Err = RecordError>>(&self) -> Result<T, RecordError> {
+        self.text.parse().map_err(|e| self.record_error(e))
+    }
+
+    fn int_error(&self, err: IntError) -> IntError {
+        let line = err.line() + self.line - 1;
+        at_line(err, line)
+    }
+
+    fn record_error(&self, mut err: RecordError) -> RecordError {
+        err.line += self.line - 1;
+        err
+    }
+}
+
+/// Splits `input` into exactly `N` sections separated by blank lines.
+/// Windows line endings are normalised to `\n` first and blank lines
+/// around the sections are ignored.
+///
+/// # Example
+/// ```
+/// # use lib::parse::{SectionError, sections};
+/// let input = "47|53\r\n97|13\r\n\r\n75,47,61\r\n\r\n";
+///
+/// let [rules, updates] = sections(input).unwrap();
+/// assert_eq!(rules.text(), "47|53\n97|13");
+/// assert_eq!(rules.fixed_lines::<2, u32>().unwrap(), [[47, 53], [97, 13]]);
+/// assert_eq!(updates.line(), 4);
+/// assert_eq!(updates.int_lines::<u32>().unwrap(), [vec![75, 47, 61]]);
+///
+/// assert_eq!(
+///     sections::<3>(input).unwrap_err(),
+///     SectionError::Count { expected: 3, found: 2 }
+/// );
+/// ```
+pub fn sections<const N: usize>(input: &str) -> Result<[Section<'_>; N], SectionError> {
+    const { assert!(N > 0, "an input consists of at least one section") };
+
+    let text = if input.contains("\r\n") {
+        Cow::Owned(input.replace("\r\n", "\n"))
+    } else {
+        Cow::Borrowed(input)
+    };
+
+    let sections: Vec<_> = blocks(&text)
+        .into_iter()
+        .map(|(line, range)| Section {
+            text: match &text {
+                Cow::Borrowed(s) => Cow::Borrowed(&s[range]),
+                Cow::Owned(s) => Cow::Owned(s[range].to_owned()),
+            },
+            line,
+        })
+        .collect();
+
+    let found = sections.len();
+    sections
+        .try_into()
+        .map_err(|_| SectionError::Count { expected: N, found })
+}
+
+/// Types a single line of a section can be parsed into, see
+/// [`FromSection`].
+///
+/// Implemented for [records](crate::record) and for lists of all integers
+/// in the line.
+pub trait FromLine: Sized {
+    /// Parses `line`, reporting errors on line 1.
+    fn from_line(line: &str) -> Result<Self, SectionError>;
+}
+
+impl<T: Record> FromLine for T {
+    fn from_line(line: &str) -> Result<Self, SectionError> {
+        line.parse().map_err(SectionError::Record)
+    }
+}
+
+impl<T: Int> FromLine for Vec<T> {
+    fn from_line(line: &str) -> Result<Self, SectionError> {
+        all_ints(line).map_err(SectionError::Int)
+    }
+}
+
+/// Types a section of an input can be parsed into, see [`FromSections`].
+///
+/// Implemented for [records](crate::record) parsed from the whole text of
+/// the section and for a `Vec` with one [`FromLine`] value per line.
+pub trait FromSection: Sized {
+    fn from_section(section: &Section) -> Result<Self, SectionError>;
+}
+
+impl<T: Record> FromSection for T {
+    fn from_section(section: &Section) -> Result<Self, SectionError> {
+        section.parse().map_err(SectionError::Record)
+    }
+}
+
+impl<T: FromLine> FromSection for Vec<T> {
+    fn from_section(section: &Section) -> Result<Self, SectionError> {
+        section
+            .text
+            .lines()
+            .enumerate()
+            .map(|(i, line)| T::from_line(line).map_err(|e| e.at_line(section.line + i)))
+            .collect()
+    }
+}
+
+/// Types an input of blank-line separated sections is parsed into by
+/// [`typed_sections`].
+///
+/// Implemented for tuples of up to four [`FromSection`] types, one per
+/// section.
+pub trait FromSections: Sized {
+    fn from_sections(input: &str) -> Result<Self, SectionError>;
+}
+
+macro_rules! impl_from_sections {
+    ($n: literal: $($t: ident $section: ident),+) => {
+        impl<$($t: FromSection),+> FromSections for ($($t,)+) {
+            fn from_sections(input: &str) -> Result<Self, SectionError> {
+                let [$($section),+] = sections::<$n>(input)?;
+                Ok(($($t::from_section(&$section)?,)+))
+            }
+        }
+    };
+}
+
+impl_from_sections!(1: A a);
+impl_from_sections!(2: A a, B b);
+impl_from_sections!(3: A a, B b, C c);
+impl_from_sections!(4: A a, B b, C c, D d);
+
+/// Splits `input` into [`sections`] and parses each into the type at its
+/// position in the tuple `T`.
+///
+/// # Example
+/// ```
+/// # use lib::{parse::typed_sections, record::AocParse};
+/// #[derive(Debug, AocParse)]
+/// #[aoc(pattern = "{0}: {1}")]
+/// struct Field(String, u32);
+///
+/// #[derive(Debug, AocParse)]
+/// #[aoc(pattern = "your ticket:\n{0: ','}")]
+/// struct Ticket(Vec<u32>);
+///
+/// let input = "class: 1\nrow: 6\r\n\r\nyour ticket:\n7,1\n\n7,3,47\n40,4\n";
+/// let (fields, Ticket(mine), others): (Vec<Field>, Ticket, Vec<Vec<u32>>) =
+///     typed_sections(input).unwrap();
+/// assert_eq!(fields.len(), 2);
+/// assert_eq!(mine, [7, 1]);
+/// assert_eq!(others, [vec![7, 3, 47], vec![40, 4]]);
+///
+/// let err = typed_sections::<(Vec<Field>, Vec<Vec<u8>>)>("class: 1\n\n7,1\n7,300").unwrap_err();
+/// assert_eq!(err.to_string(), "line 4: invalid integer '300'");
+/// let err = typed_sections::<(Vec<Field>, Ticket)>("class: 1\nrow 6\n\n7,1").unwrap_err();
+/// assert_eq!(err.to_string(), "line 2, column 6: expected \": \"");
+/// ```
+pub fn typed_sections<T: FromSections>(input: &str) -> Result<T, SectionError> {
+    T::from_sections(input)
+}
+
+/// Returns the 1-based number of the first line and the byte range of each
+/// run of non-blank lines in `text`.
+fn blocks(text: &str) -> Vec<(usize, Range<usize>)> {
+    let mut blocks: Vec<(usize, Range<usize>)> = vec![];
+    let mut in_block = false;
+    let mut start = 0;
+
+    for (i, line) in text.split('\n').enumerate() {
+        let end = start + line.len();
+        if line.trim().is_empty() {
+            in_block = false;
+        } else if in_block {
+            blocks.last_mut().unwrap().1.end = end;
+        } else {
+            blocks.push((i + 1, start..end));
+            in_block = true;
+        }
+        start = end + 1;
+    }
+    blocks
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_signs() {
+        assert_eq!(all_ints::<i64>("1 -2 --3 -x -"), Ok(vec![1, -2, -3]));
+        assert_eq!(
+            all_ints::<u64>("1 -2 --3 -x -"),
+            Err(IntError::Invalid {
+                line: 1,
+                token: "-2".into()
+            })
+        );
+        assert_eq!(
+            ints::<2, u32>("3 -4"),
+            Err(IntError::Invalid {
+                line: 1,
+                token: "-4".into()
+            })
+        );
+        assert_eq!(all_ints::<u8>(""), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_ranges() {
+        assert_eq!(all_ints::<u32>("3-7,10-12"), Ok(vec![3, 7, 10, 12]));
+        assert_eq!(all_ints::<i32>("3-7 -3--1"), Ok(vec![3, 7, -3, -1]));
+        assert_eq!(ints::<2, u8>("x=-3-7").unwrap_err().line(), 1);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            ints::<2, u8>("1 2 3"),
+            Err(IntError::Count {
+                line: 1,
+                expected: 2,
+                found: 3
+            })
+        );
+        assert_eq!(
+            int_lines::<i8>("1\n2\n-129").unwrap_err().to_string(),
+            "line 3: invalid integer '-129'"
+        );
+        assert_eq!(columns::<2, u32>("1 2\n3 x").unwrap_err().line(), 2);
+        assert_eq!(
+            all_ints::<u8>("99999999999999999999999"),
+            Err(IntError::Invalid {
+                line: 1,
+                token: "99999999999999999999999".into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_sections() {
+        let input = "\n1 2\n3 4\n \n\n5\n6,x\n\n";
+        let [a, b] = sections(input).unwrap();
+        assert_eq!((a.line(), a.text()), (2, "1 2\n3 4"));
+        assert_eq!((b.line(), b.text()), (6, "5\n6,x"));
+        assert_eq!(a.columns::<2, u8>().unwrap(), [vec![1, 3], vec![2, 4]]);
+        assert_eq!(b.int_lines::<u8>(), Ok(vec![vec![5], vec![6]]));
+        assert_eq!(b.fixed_lines::<2, u8>().unwrap_err().line(), 6);
+
+        assert_eq!(sections::<1>("a\r\nb\r\n").unwrap()[0].text(), "a\nb");
+        assert_eq!(
+            sections::<2>(" \n"),
+            Err(SectionError::Count {
+                expected: 2,
+                found: 0
+            })
+        );
+    }
+}
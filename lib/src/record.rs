@@ -0,0 +1,393 @@
+//! Parsing of puzzle records described by a pattern.
+//!
+//! `#[derive(AocParse)]` generates a [`FromStr`] implementation from a
+//! pattern like `{result}: {components: ' '}`. Text outside of braces has to
+//! appear literally, `{{` and `}}` stand for literal braces. Each field of
+//! the type appears once as a placeholder, by name or by index for tuple
+//! structs, and is parsed with its own [`FromStr`] implementation. A field
+//! ends where the text following it in the pattern starts, or at the end of
+//! the record.
+//!
+//! Separators in single quotes after the field name split the field into a
+//! `Vec`, nested ones like `{rows: '\n' ','}` into a `Vec<Vec<_>>`. A
+//! trailing separator is ignored and an empty field gives an empty `Vec`.
+//!
+//! Enums carry a pattern on each variant. The first variant whose pattern
+//! starts with the text in front of its first placeholder is parsed.
+//!
+//! The derive also implements the [`Record`] marker trait.
+//!
+//! # Example
+//! ```
+//! use lib::record::{AocParse, RecordError, RecordErrorKind};
+//!
+//! #[derive(Debug, PartialEq, AocParse)]
+//! #[aoc(pattern = "{result}: {components: ' '}")]
+//! struct Equation {
+//!     result: u64,
+//!     components: Vec<u64>,
+//! }
+//!
+//! #[derive(Debug, PartialEq, AocParse)]
+//! enum Instruction {
+//!     #[aoc(pattern = "cpy {0} {1}")]
+//!     Copy(i64, char),
+//!     #[aoc(pattern = "jnz {offset}")]
+//!     Jump { offset: i64 },
+//!     #[aoc(pattern = "nop")]
+//!     Noop,
+//! }
+//!
+//! let equation: Equation = "190: 10 19".parse().unwrap();
+//! assert_eq!(equation, Equation { result: 190, components: vec![10, 19] });
+//!
+//! let err = "190: 10 x19".parse::<Equation>().unwrap_err();
+//! assert_eq!((err.line, err.column), (1, 9));
+//! assert_eq!(
+//!     err.to_string(),
+//!     "line 1, column 9: invalid components \"x19\": invalid digit found in string"
+//! );
+//!
+//! assert_eq!("cpy -2 a".parse(), Ok(Instruction::Copy(-2, 'a')));
+//! assert_eq!("jnz 3".parse(), Ok(Instruction::Jump { offset: 3 }));
+//! assert_eq!(
+//!     "jmp 3".parse::<Instruction>().unwrap_err().kind,
+//!     RecordErrorKind::Variant(vec!["cpy ".into(), "jnz ".into(), "nop".into()])
+//! );
+//! ```
+
+use core::fmt;
+use std::{any::Any, error::Error, str::FromStr};
+
+pub use aoc_derive::AocParse;
+
+/// Types implementing [`FromStr`] from a record pattern, usually by
+/// `#[derive(AocParse)]`.
+///
+/// Used by [`typed_sections`](crate::parse::typed_sections) to tell records
+/// apart from other section contents.
+pub trait Record: FromStr<Err = RecordError> {}
+
+/// Error returned when a record does not match its pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordError {
+    /// 1-based line of the position.
+    pub line: usize,
+    /// 1-based column of the position, counted in characters.
+    pub column: usize,
+    pub kind: RecordErrorKind,
+}
+
+/// What went wrong at the position of a [`RecordError`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordErrorKind {
+    /// The text of the pattern has been expected.
+    Literal(String),
+    /// The record continues after the end of the pattern.
+    Trailing,
+    /// The record starts with none of the prefixes of the enum variants.
+    Variant(Vec<String>),
+    /// The value of a field could not be parsed.
+    Field {
+        field: String,
+        value: String,
+        reason: String,
+    },
+}
+
+impl fmt::Display for RecordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: ", self.line, self.column)?;
+        match &self.kind {
+            RecordErrorKind::Literal(literal) => write!(f, "expected {literal:?}"),
+            RecordErrorKind::Trailing => write!(f, "expected the end of the record"),
+            RecordErrorKind::Variant(prefixes) => {
+                let prefixes: Vec<_> = prefixes.iter().map(|p| format!("{p:?}")).collect();
+                write!(f, "expected one of {}", prefixes.join(", "))
+            }
+            RecordErrorKind::Field {
+                field,
+                value,
+                reason,
+            } => write!(f, "invalid {field} {value:?}: {reason}"),
+        }
+    }
+}
+
+impl Error for RecordError {}
+
+/// Parses each non-blank line of `input` as a record, reporting errors
+/// with the line number in `input`.
+///
+/// # Example
+/// ```
+/// # use lib::record::{AocParse, records};
+/// #[derive(Debug, AocParse)]
+/// #[aoc(pattern = "{0}|{1}")]
+/// struct Rule(u32, u32);
+///
+/// assert_eq!(records::<Rule>("47|53\n\n97|13\n").unwrap().len(), 2);
+/// assert_eq!(
+///     records::<Rule>("47|53\n97,13").unwrap_err().to_string(),
+///     "line 2, column 6: expected \"|\""
+/// );
+/// ```
+pub fn records<T: FromStr<Err = RecordError>>(input: &str) -> Result<Vec<T>, RecordError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty())
+        .map(|(i, l)| {
+            l.parse().map_err(|mut err: RecordError| {
+                err.line += i;
+                err
+            })
+        })
+        .collect()
+}
+
+/// Runtime support of the code generated by `#[derive(AocParse)]`.
+#[doc(hidden)]
+pub mod __private {
+    use super::*;
+
+    /// Line and column of a position in a record.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Pos {
+        line: usize,
+        column: usize,
+    }
+
+    impl Pos {
+        /// Returns the position after `text` starting at this position.
+        fn advance(self, text: &str) -> Self {
+            match text.rsplit_once('\n') {
+                Some((before, after)) => Self {
+                    line: self.line + before.matches('\n').count() + 1,
+                    column: after.chars().count() + 1,
+                },
+                None => Self {
+                    line: self.line,
+                    column: self.column + text.chars().count(),
+                },
+            }
+        }
+
+        fn error(self, kind: RecordErrorKind) -> RecordError {
+            RecordError {
+                line: self.line,
+                column: self.column,
+                kind,
+            }
+        }
+    }
+
+    /// Matches the pieces of a pattern against a record from left to right.
+    pub struct Scanner<'a> {
+        input: &'a str,
+        offset: usize,
+    }
+
+    impl<'a> Scanner<'a> {
+        pub fn new(input: &'a str) -> Self {
+            Self { input, offset: 0 }
+        }
+
+        fn rest(&self) -> &'a str {
+            &self.input[self.offset..]
+        }
+
+        fn pos(&self) -> Pos {
+            Pos { line: 1, column: 1 }.advance(&self.input[..self.offset])
+        }
+
+        pub fn starts_with(&self, prefix: &str) -> bool {
+            self.rest().starts_with(prefix)
+        }
+
+        /// Consumes `literal`, which has to follow directly.
+        pub fn literal(&mut self, literal: &str) -> Result<(), RecordError> {
+            if !self.starts_with(literal) {
+                return Err(self.pos().error(RecordErrorKind::Literal(literal.into())));
+            }
+            self.offset += literal.len();
+            Ok(())
+        }
+
+        /// Consumes the value of a field up to the next occurrence of `end`,
+        /// or up to the end of the record if there is no text following the
+        /// field, and returns it with its position.
+        pub fn until(&mut self, end: Option<&str>) -> Result<(&'a str, Pos), RecordError> {
+            let rest = self.rest();
+            let len = match end {
+                Some(end) => rest.find(end).ok_or_else(|| {
+                    let pos = self.pos().advance(rest);
+                    pos.error(RecordErrorKind::Literal(end.into()))
+                })?,
+                None => rest.len(),
+            };
+            let pos = self.pos();
+            self.offset += len;
+            Ok((&rest[..len], pos))
+        }
+
+        /// Checks that the whole record has been consumed.
+        pub fn end(&self) -> Result<(), RecordError> {
+            match self.rest() {
+                "" => Ok(()),
+                _ => Err(self.pos().error(RecordErrorKind::Trailing)),
+            }
+        }
+
+        /// Returns the error for a record matching none of the variants.
+        pub fn variant(&self, prefixes: &[&str]) -> RecordError {
+            let prefixes = prefixes.iter().map(|&p| p.into()).collect();
+            self.pos().error(RecordErrorKind::Variant(prefixes))
+        }
+    }
+
+    /// Parses the value of a field at position `at`.
+    pub fn field<T>(value: &str, at: Pos, name: &str) -> Result<T, RecordError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display + 'static,
+    {
+        value.parse().map_err(|err: T::Err| {
+            // Errors of nested records are moved to their position in the
+            // outer record.
+            if let Some(err) = (&err as &dyn Any).downcast_ref::<RecordError>() {
+                let pos = match err.line {
+                    1 => Pos {
+                        line: at.line,
+                        column: at.column + err.column - 1,
+                    },
+                    line => Pos {
+                        line: at.line + line - 1,
+                        column: err.column,
+                    },
+                };
+                return pos.error(err.kind.clone());
+            }
+            at.error(RecordErrorKind::Field {
+                field: name.into(),
+                value: value.into(),
+                reason: err.to_string(),
+            })
+        })
+    }
+
+    /// Splits the value of a field at position `at` at each `separator`
+    /// and parses the parts with `parse`.
+    pub fn split<T>(
+        value: &str,
+        at: Pos,
+        separator: &str,
+        parse: impl Fn(&str, Pos) -> Result<T, RecordError>,
+    ) -> Result<Vec<T>, RecordError> {
+        let value = value.strip_suffix(separator).unwrap_or(value);
+        if value.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut at = at;
+        value
+            .split(separator)
+            .map(|part| {
+                let res = parse(part, at);
+                at = at.advance(part).advance(separator);
+                res
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug, PartialEq, AocParse)]
+    #[aoc(pattern = "{0},{1}")]
+    struct Point(i32, i32);
+
+    #[derive(Debug, PartialEq, AocParse)]
+    #[aoc(pattern = "{name}:\n{points: '\n' ' '}")]
+    struct Shape {
+        name: String,
+        points: Vec<Vec<Point>>,
+    }
+
+    #[derive(Debug, PartialEq, AocParse)]
+    enum Command {
+        #[aoc(pattern = "move {{{0}}}")]
+        Move(Point),
+        #[aoc(pattern = "{0}")]
+        Say(String),
+    }
+
+    #[test]
+    fn test_nested() {
+        let shape: Shape = "square:\n0,0 0,1\n1,1 1,0\n".parse().unwrap();
+        assert_eq!(shape.name, "square");
+        assert_eq!(
+            shape.points,
+            [
+                vec![Point(0, 0), Point(0, 1)],
+                vec![Point(1, 1), Point(1, 0)]
+            ]
+        );
+
+        let empty: Shape = "none:\n".parse().unwrap();
+        assert!(empty.points.is_empty());
+
+        let err = "square:\n0,0 0,1\n1,1 1;0".parse::<Shape>().unwrap_err();
+        assert_eq!(
+            err,
+            RecordError {
+                line: 3,
+                column: 8,
+                kind: RecordErrorKind::Literal(",".into())
+            }
+        );
+        let err = "square:\n0,0 0,x".parse::<Shape>().unwrap_err();
+        assert_eq!((err.line, err.column), (2, 7));
+        assert!(matches!(err.kind, RecordErrorKind::Field { .. }));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            "1,2,3".parse::<Point>().unwrap_err().kind,
+            RecordErrorKind::Field {
+                field: "1".into(),
+                value: "2,3".into(),
+                reason: "invalid digit found in string".into()
+            }
+        );
+        assert_eq!(
+            "square\n".parse::<Shape>().unwrap_err(),
+            RecordError {
+                line: 2,
+                column: 1,
+                kind: RecordErrorKind::Literal(":\n".into())
+            }
+        );
+        assert_eq!(
+            records::<Point>("1,2\n\n3").unwrap_err().to_string(),
+            "line 3, column 2: expected \",\""
+        );
+    }
+
+    #[test]
+    fn test_enum() {
+        assert_eq!("move {1,-2}".parse(), Ok(Command::Move(Point(1, -2))));
+        assert_eq!("hi".parse(), Ok(Command::Say("hi".into())));
+        assert_eq!(
+            "move {1,-2}}".parse::<Command>().unwrap_err(),
+            RecordError {
+                line: 1,
+                column: 12,
+                kind: RecordErrorKind::Trailing
+            }
+        );
+    }
+}
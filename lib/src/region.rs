@@ -0,0 +1,215 @@
+//! Segmentation of grids into connected regions of equal cells.
+
+use crate::{Direction, Grid, Pos};
+
+/// A connected region of orthogonally adjacent cells with equal values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region<T> {
+    id: usize,
+    value: T,
+    cells: Vec<Pos>,
+    perimeter: usize,
+    sides: usize,
+    min: Pos,
+    max: Pos,
+}
+
+impl<T> Region<T> {
+    /// Returns the label of the region, which is its index in
+    /// [`Segmentation::regions`].
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Returns the value all cells of the region share.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Returns the positions of all cells of the region in the order they
+    /// were discovered.
+    pub fn cells(&self) -> &[Pos] {
+        &self.cells
+    }
+
+    /// Returns the number of cells of the region.
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Returns the number of cell edges which border a different region or
+    /// the outside of the grid.
+    pub fn perimeter(&self) -> usize {
+        self.perimeter
+    }
+
+    /// Returns the number of straight sides of the region's outline, which
+    /// is equal to the number of its corners.
+    pub fn sides(&self) -> usize {
+        self.sides
+    }
+
+    /// Returns the top left and bottom right corner of the smallest
+    /// rectangle containing all cells of the region.
+    pub fn bounding_box(&self) -> (Pos, Pos) {
+        (self.min, self.max)
+    }
+}
+
+/// Result of [`segment`] containing all regions of a grid together with a
+/// label grid mapping every cell to the id of its region.
+#[derive(Debug, Clone)]
+pub struct Segmentation<T> {
+    labels: Grid<usize>,
+    regions: Vec<Region<T>>,
+}
+
+impl<T> Segmentation<T> {
+    /// Returns all regions ordered by their id.
+    pub fn regions(&self) -> &[Region<T>] {
+        &self.regions
+    }
+
+    /// Returns a grid with the same dimensions as the segmented grid which
+    /// contains the region id of every cell.
+    pub fn labels(&self) -> &Grid<usize> {
+        &self.labels
+    }
+
+    /// Returns the region the cell at `pos` belongs to, if `pos` is in the
+    /// bounds of the grid.
+    pub fn region_at(&self, pos: Pos) -> Option<&Region<T>> {
+        self.labels.get(pos).map(|&id| &self.regions[id])
+    }
+}
+
+/// Labels every connected region of orthogonally adjacent equal cells of
+/// `grid` and computes the metrics of each region in a single pass over
+/// the grid.
+///
+/// # Example
+/// ```
+/// # use lib::{Grid, Pos, region::segment};
+/// let grid: Grid<char> = "\
+/// AAAA
+/// BBCD
+/// BBCC
+/// EEEC".parse().unwrap();
+///
+/// let seg = segment(&grid);
+/// assert_eq!(seg.regions().len(), 5);
+///
+/// let c = seg.region_at(Pos { x: 2, y: 1 }).unwrap();
+/// assert_eq!(*c.value(), 'C');
+/// assert_eq!(c.area(), 4);
+/// assert_eq!(c.perimeter(), 10);
+/// assert_eq!(c.sides(), 8);
+/// assert_eq!(c.bounding_box(), (Pos { x: 2, y: 1 }, Pos { x: 3, y: 3 }));
+/// ```
+pub fn segment<T>(grid: &Grid<T>) -> Segmentation<T>
+where
+    T: PartialEq + Clone,
+{
+    let mut labels = Grid::filled(grid.width(), grid.height(), usize::MAX);
+    let mut regions = vec![];
+
+    for start in grid.positions() {
+        if labels[start] != usize::MAX {
+            continue;
+        }
+
+        let id = regions.len();
+        let value = &grid[start];
+        labels[start] = id;
+
+        let mut region = Region {
+            id,
+            value: value.clone(),
+            cells: vec![],
+            perimeter: 0,
+            sides: 0,
+            min: start,
+            max: start,
+        };
+        let mut stack = vec![start];
+
+        while let Some(pos) = stack.pop() {
+            region.cells.push(pos);
+            region.min = (region.min.x.min(pos.x), region.min.y.min(pos.y)).into();
+            region.max = (region.max.x.max(pos.x), region.max.y.max(pos.y)).into();
+
+            let same = |p: Pos| grid.get(p) == Some(value);
+
+            for dir in Direction::all() {
+                let next = pos.mv(dir);
+                if !same(next) {
+                    region.perimeter += 1;
+                } else if labels[next] == usize::MAX {
+                    labels[next] = id;
+                    stack.push(next);
+                }
+
+                // Every corner of the outline is counted once from the cell
+                // it belongs to. An outer corner is formed by two edges of the
+                // cell, an inner corner by two equal neighbours with a
+                // different cell in between.
+                let side = pos.mv(dir.turn_cw());
+                match (same(next), same(side)) {
+                    (false, false) => region.sides += 1,
+                    (true, true) if !same(next.mv(dir.turn_cw())) => region.sides += 1,
+                    _ => {}
+                }
+            }
+        }
+
+        regions.push(region);
+    }
+
+    Segmentation { labels, regions }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn fence_costs(input: &str) -> (usize, usize) {
+        let grid: Grid<char> = input.parse().unwrap();
+        let seg = segment(&grid);
+        seg.regions().iter().fold((0, 0), |(p, s), r| {
+            (p + r.area() * r.perimeter(), s + r.area() * r.sides())
+        })
+    }
+
+    #[test]
+    fn test_enclosed_regions() {
+        let input = "\
+OOOOO
+OXOXO
+OOOOO
+OXOXO
+OOOOO";
+        assert_eq!(fence_costs(input), (772, 436));
+    }
+
+    #[test]
+    fn test_inner_corners() {
+        let input = "\
+AAAAAA
+AAABBA
+AAABBA
+ABBAAA
+ABBAAA
+AAAAAA";
+        assert_eq!(fence_costs(input).1, 368);
+    }
+
+    #[test]
+    fn test_labels() {
+        let grid: Grid<char> = "ABA\nAAA".parse().unwrap();
+        let seg = segment(&grid);
+        assert_eq!(seg.regions().len(), 2);
+        assert_eq!(seg.labels().to_string(), "010\n000");
+        assert_eq!(seg.regions()[0].sides(), 8);
+        assert_eq!(seg.regions()[1].sides(), 4);
+    }
+}
@@ -0,0 +1,521 @@
+//! Generic graph search algorithms over implicit graphs.
+//!
+//! Graphs are never built explicitly. Instead, every search takes a start
+//! node and a `successors` closure which returns the neighbours of a given
+//! node (and the cost to get there, for weighted searches).
+
+use crate::{Direction, Grid, Pos};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// Cost of an edge or path in a weighted search.
+///
+/// The [`Default`] value is used as the cost of the empty path.
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<T> Cost for T where T: Copy + Ord + Add<Output = Self> + Default {}
+
+/// Result of a full search from a single start node.
+///
+/// Contains the shortest distance to every reached node, all predecessors
+/// of a node which lie on a shortest path to it and the number of distinct
+/// shortest paths to every node.
+#[derive(Debug, Clone)]
+pub struct SearchResult<N, C = usize> {
+    start: N,
+    dist: HashMap<N, C>,
+    preds: HashMap<N, Vec<N>>,
+    counts: HashMap<N, usize>,
+}
+
+impl<N, C> SearchResult<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+{
+    fn new(start: N) -> Self {
+        Self {
+            dist: HashMap::from([(start.clone(), C::default())]),
+            preds: HashMap::new(),
+            counts: HashMap::from([(start.clone(), 1)]),
+            start,
+        }
+    }
+
+    /// Relaxes the edge `from -> to` with the total cost `cost`. Returns true if
+    /// `to` has been reached with a new lower cost.
+    fn relax(&mut self, from: &N, to: N, cost: C) -> bool {
+        let from_count = self.counts[from];
+        match self.dist.get(&to) {
+            Some(&d) if cost > d => false,
+            Some(&d) if cost == d => {
+                if to != self.start {
+                    self.preds.entry(to.clone()).or_default().push(from.clone());
+                    *self.counts.entry(to).or_default() += from_count;
+                }
+                false
+            }
+            _ => {
+                self.dist.insert(to.clone(), cost);
+                self.preds.insert(to.clone(), vec![from.clone()]);
+                self.counts.insert(to, from_count);
+                true
+            }
+        }
+    }
+
+    /// Returns the start node of the search.
+    pub fn start(&self) -> &N {
+        &self.start
+    }
+
+    /// Returns the shortest distance from the start to `node`, if it has
+    /// been reached.
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.dist.get(node).copied()
+    }
+
+    /// Returns the map of shortest distances of all reached nodes.
+    pub fn distances(&self) -> &HashMap<N, C> {
+        &self.dist
+    }
+
+    /// Returns all nodes which precede `node` on any shortest path from the
+    /// start to `node`.
+    pub fn predecessors(&self, node: &N) -> &[N] {
+        self.preds.get(node).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Returns the map of predecessors of all reached nodes.
+    pub fn predecessor_map(&self) -> &HashMap<N, Vec<N>> {
+        &self.preds
+    }
+
+    /// Returns the number of distinct shortest paths from the start to
+    /// `node`, or 0 if the node has not been reached.
+    pub fn path_count(&self, node: &N) -> usize {
+        self.counts.get(node).copied().unwrap_or_default()
+    }
+
+    /// Returns true if `node` has been reached from the start.
+    pub fn is_reached(&self, node: &N) -> bool {
+        self.dist.contains_key(node)
+    }
+
+    /// Returns an iterator over all reached nodes with their distances.
+    pub fn reached(&self) -> impl Iterator<Item = (&N, C)> {
+        self.dist.iter().map(|(n, c)| (n, *c))
+    }
+
+    /// Reconstructs one shortest path from the start to `node`, including
+    /// both ends.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        if !self.is_reached(node) {
+            return None;
+        }
+
+        let mut path = vec![node.clone()];
+        let mut curr = node;
+        while let Some(prev) = self.predecessors(curr).first() {
+            path.push(prev.clone());
+            curr = prev;
+        }
+        path.reverse();
+
+        Some(path)
+    }
+
+    /// Returns the set of all nodes which lie on any shortest path from the
+    /// start to `node`, including both ends.
+    pub fn nodes_on_paths_to(&self, node: &N) -> HashSet<N> {
+        let mut res = HashSet::new();
+        if !self.is_reached(node) {
+            return res;
+        }
+
+        let mut stack = vec![node.clone()];
+        while let Some(n) = stack.pop() {
+            if res.insert(n.clone()) {
+                stack.extend(self.predecessors(&n).iter().cloned());
+            }
+        }
+
+        res
+    }
+}
+
+/// Runs a breadth-first search from `start` over the whole reachable graph,
+/// where every edge has a cost of 1.
+///
+/// # Example
+/// ```
+/// # use lib::search::bfs;
+/// // Two paths from 1 to 4: 1 -> 2 -> 4 and 1 -> 3 -> 4.
+/// let res = bfs(1, |&n| match n {
+///     1 => vec![2, 3],
+///     2 | 3 => vec![4],
+///     _ => vec![],
+/// });
+///
+/// assert_eq!(res.distance(&4), Some(2));
+/// assert_eq!(res.path_count(&4), 2);
+/// assert_eq!(res.path_to(&4), Some(vec![1, 2, 4]));
+/// assert_eq!(res.distance(&5), None);
+/// ```
+pub fn bfs<N, I>(start: N, mut successors: impl FnMut(&N) -> I) -> SearchResult<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut res = SearchResult::new(start.clone());
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((node, dist)) = queue.pop_front() {
+        for next in successors(&node) {
+            if res.relax(&node, next.clone(), dist + 1) {
+                queue.push_back((next, dist + 1));
+            }
+        }
+    }
+
+    res
+}
+
+/// Runs a depth-first search from `start` and returns all reachable nodes in
+/// the order they were first visited.
+///
+/// # Example
+/// ```
+/// # use lib::search::dfs;
+/// let order = dfs(1, |&n| match n {
+///     1 => vec![2, 3],
+///     2 => vec![4],
+///     _ => vec![],
+/// });
+/// assert_eq!(order, vec![1, 2, 4, 3]);
+/// ```
+pub fn dfs<N, I>(start: N, mut successors: impl FnMut(&N) -> I) -> Vec<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+    I::IntoIter: DoubleEndedIterator,
+{
+    let mut seen = HashSet::new();
+    let mut order = vec![];
+    let mut stack = vec![start];
+
+    while let Some(node) = stack.pop() {
+        if !seen.insert(node.clone()) {
+            continue;
+        }
+        // Successors are pushed in reverse so that they are visited in the
+        // order they were returned.
+        stack.extend(
+            successors(&node)
+                .into_iter()
+                .rev()
+                .filter(|n| !seen.contains(n)),
+        );
+        order.push(node);
+    }
+
+    order
+}
+
+/// Runs Dijkstra's algorithm from `start` over the whole reachable graph.
+///
+/// `successors` returns the neighbours of a node together with the cost of
+/// the edge to them. Path counts are only correct if all edge costs are
+/// strictly positive.
+///
+/// # Example
+/// ```
+/// # use lib::search::dijkstra;
+/// let res = dijkstra('a', |&n| match n {
+///     'a' => vec![('b', 1), ('c', 5)],
+///     'b' => vec![('c', 2)],
+///     _ => vec![],
+/// });
+///
+/// assert_eq!(res.distance(&'c'), Some(3));
+/// assert_eq!(res.path_to(&'c'), Some(vec!['a', 'b', 'c']));
+/// ```
+pub fn dijkstra<N, C, I>(start: N, mut successors: impl FnMut(&N) -> I) -> SearchResult<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut res = SearchResult::new(start.clone());
+    let mut done = HashSet::new();
+    // Nodes are kept in a separate list so that `N` does not need to be `Ord`.
+    let mut nodes = vec![start];
+    let mut heap = BinaryHeap::from([(Reverse(C::default()), Reverse(0))]);
+
+    while let Some((Reverse(cost), Reverse(idx))) = heap.pop() {
+        let node = nodes[idx].clone();
+        if !done.insert(node.clone()) {
+            continue;
+        }
+
+        for (next, step) in successors(&node) {
+            if done.contains(&next) {
+                continue;
+            }
+            if res.relax(&node, next.clone(), cost + step) {
+                heap.push((Reverse(cost + step), Reverse(nodes.len())));
+                nodes.push(next);
+            }
+        }
+    }
+
+    res
+}
+
+/// Runs an A* search from `start` until a node satisfying `is_goal` is
+/// reached and returns the path to it including both ends together with
+/// its cost.
+///
+/// Nodes are not expanded again once they have been expanded, so
+/// `heuristic` has to be consistent: it must never overestimate the
+/// remaining cost to a goal, and for every step from a node to a successor
+/// it must not drop by more than the cost of that step. Otherwise the
+/// returned path might not be the cheapest one.
+///
+/// # Example
+/// ```
+/// # use lib::{Pos, Direction, search::astar};
+/// let goal = Pos { x: 3, y: 2 };
+/// let (path, cost) = astar(
+///     Pos { x: 0, y: 0 },
+///     |&p: &Pos| [Direction::Right, Direction::Down].map(|d| (p.mv(d), 1)),
+///     |p| p.manhattan_distance(goal),
+///     |p| *p == goal,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(cost, 5);
+/// assert_eq!(path.len(), 6);
+/// ```
+pub fn astar<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut dist = HashMap::from([(start.clone(), C::default())]);
+    let mut preds: HashMap<N, N> = HashMap::new();
+    let mut done = HashSet::new();
+    let mut heap = BinaryHeap::from([(Reverse(heuristic(&start)), Reverse(0))]);
+    let mut nodes = vec![start];
+
+    while let Some((_, Reverse(idx))) = heap.pop() {
+        let node = nodes[idx].clone();
+        if !done.insert(node.clone()) {
+            continue;
+        }
+
+        let cost = dist[&node];
+        if is_goal(&node) {
+            let mut path = vec![node];
+            while let Some(prev) = preds.get(path.last().unwrap()) {
+                path.push(prev.clone());
+            }
+            path.reverse();
+            return Some((path, cost));
+        }
+
+        for (next, step) in successors(&node) {
+            let next_cost = cost + step;
+            if dist.get(&next).is_some_and(|&d| d <= next_cost) {
+                continue;
+            }
+            dist.insert(next.clone(), next_cost);
+            preds.insert(next.clone(), node.clone());
+            heap.push((Reverse(next_cost + heuristic(&next)), Reverse(nodes.len())));
+            nodes.push(next);
+        }
+    }
+
+    None
+}
+
+/// Runs a breadth-first search over the orthogonal neighbours of a grid
+/// starting at `start`. Moving from one cell to a neighbouring cell is only
+/// allowed if `can_move(from, to)` returns true.
+///
+/// # Example
+/// ```
+/// # use lib::{Grid, Pos, search::grid_bfs};
+/// let grid: Grid<char> = "\
+/// ..#
+/// .##
+/// ...".parse().unwrap();
+///
+/// let res = grid_bfs(&grid, Pos { x: 0, y: 0 }, |_, to| *to != '#');
+/// assert_eq!(res.distance(&Pos { x: 2, y: 2 }), Some(4));
+/// assert_eq!(res.distance(&Pos { x: 2, y: 0 }), None);
+/// ```
+pub fn grid_bfs<T>(
+    grid: &Grid<T>,
+    start: Pos,
+    can_move: impl Fn(&T, &T) -> bool,
+) -> SearchResult<Pos, usize> {
+    bfs(start, |&p| {
+        let from = &grid[p];
+        grid.neighbours(p)
+            .filter(|n| can_move(from, n.value))
+            .map(|n| n.pos)
+            .collect::<Vec<_>>()
+    })
+}
+
+/// Runs Dijkstra's algorithm over a grid where the state is a position
+/// together with the direction it has been entered from.
+///
+/// For every state, `cost(pos, dir, next_dir, value)` is called for each
+/// orthogonal direction except going back, where `value` is the value of
+/// the cell in `next_dir`. It returns the cost of moving there, or `None`
+/// if the move is not possible. This allows modelling puzzles where turning
+/// costs more than going straight.
+///
+/// # Example
+/// ```
+/// # use lib::{Grid, Pos, Direction, search::grid_directed_dijkstra};
+/// let grid: Grid<char> = "\
+/// ...
+/// .#.
+/// ...".parse().unwrap();
+///
+/// // Going straight costs 1, turning costs 1000 extra.
+/// let res = grid_directed_dijkstra(
+///     &grid,
+///     (Pos { x: 0, y: 2 }, Direction::Right),
+///     |_, dir, next_dir, v| match *v {
+///         '#' => None,
+///         _ if dir == next_dir => Some(1),
+///         _ => Some(1001),
+///     },
+/// );
+///
+/// let end = res
+///     .reached()
+///     .filter(|((p, _), _)| *p == Pos { x: 2, y: 0 })
+///     .map(|(_, c)| c)
+///     .min();
+/// assert_eq!(end, Some(1004));
+/// ```
+pub fn grid_directed_dijkstra<T, C>(
+    grid: &Grid<T>,
+    start: (Pos, Direction),
+    cost: impl Fn(Pos, Direction, Direction, &T) -> Option<C>,
+) -> SearchResult<(Pos, Direction), C>
+where
+    C: Cost,
+{
+    dijkstra(start, |&(pos, dir)| {
+        [dir.turn_ccw(), dir, dir.turn_cw()]
+            .into_iter()
+            .filter_map(|next_dir| {
+                let next = pos.mv(next_dir);
+                let value = grid.get(next)?;
+                let c = cost(pos, dir, next_dir, value)?;
+                Some(((next, next_dir), c))
+            })
+            .collect::<Vec<_>>()
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bfs_path_counts() {
+        // Grid graph of 3x3 where only moving right and down is allowed.
+        // The number of paths to the opposite corner is C(4, 2) = 6.
+        let res = bfs((0, 0), |&(x, y)| {
+            let mut v = vec![];
+            if x < 2 {
+                v.push((x + 1, y));
+            }
+            if y < 2 {
+                v.push((x, y + 1));
+            }
+            v
+        });
+
+        assert_eq!(res.distance(&(2, 2)), Some(4));
+        assert_eq!(res.path_count(&(2, 2)), 6);
+        assert_eq!(res.nodes_on_paths_to(&(2, 2)).len(), 9);
+        assert_eq!(res.path_count(&(0, 0)), 1);
+    }
+
+    #[test]
+    fn test_bfs_cycle() {
+        let res = bfs(0, |&n| [(n + 1) % 4, (n + 3) % 4]);
+        assert_eq!(res.distance(&2), Some(2));
+        assert_eq!(res.path_count(&2), 2);
+        assert_eq!(res.predecessors(&0), &[] as &[i32]);
+    }
+
+    #[test]
+    fn test_dijkstra_equal_paths() {
+        let res = dijkstra(0, |&n| match n {
+            0 => vec![(1, 2), (2, 1)],
+            1 => vec![(3, 1)],
+            2 => vec![(3, 2), (1, 1)],
+            _ => vec![],
+        });
+
+        assert_eq!(res.distance(&1), Some(2));
+        assert_eq!(res.distance(&3), Some(3));
+        // 0 -> 1 -> 3, 0 -> 2 -> 1 -> 3 and 0 -> 2 -> 3
+        assert_eq!(res.path_count(&3), 3);
+        assert_eq!(res.nodes_on_paths_to(&3), HashSet::from([0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn test_astar_unreachable() {
+        let res = astar(0, |&n| (n < 5).then_some((n + 1, 1)), |_| 0, |&n| n == 10);
+        assert_eq!(res, None);
+    }
+
+    #[test]
+    fn test_astar_matches_dijkstra() {
+        let grid: Grid<char> = "\
+.....
+.###.
+...#.
+.#...
+....."
+            .parse()
+            .unwrap();
+        let start = Pos { x: 0, y: 0 };
+        let goal = Pos { x: 4, y: 4 };
+        let succ = |&p: &Pos| {
+            grid.neighbours(p)
+                .equal_to(&'.')
+                .map(|n| (n.pos, 1))
+                .collect::<Vec<_>>()
+        };
+
+        let (path, cost) =
+            astar(start, succ, |p| p.manhattan_distance(goal), |p| *p == goal).expect("path");
+        let res = dijkstra(start, succ);
+
+        assert_eq!(Some(cost), res.distance(&goal));
+        assert_eq!(path.len(), cost + 1);
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.last(), Some(&goal));
+    }
+}
@@ -0,0 +1,136 @@
+use core::fmt;
+use std::{any::Any, fmt::Display, str::FromStr};
+
+/// A solution of a single day's puzzle.
+///
+/// The puzzle input is parsed once by [`Solution::parse`] and then passed
+/// to both parts.
+///
+/// # Example
+/// ```
+/// # use lib::Solution;
+/// struct Sum;
+///
+/// impl Solution for Sum {
+///     type Input = Vec<u32>;
+///
+///     fn parse(input: &str) -> Self::Input {
+///         input.lines().map(|l| l.parse().unwrap()).collect()
+///     }
+///
+///     fn part1(input: &Self::Input) -> impl std::fmt::Display {
+///         input.iter().sum::<u32>()
+///     }
+///
+///     fn part2(input: &Self::Input) -> impl std::fmt::Display {
+///         input.iter().max().copied().unwrap_or_default()
+///     }
+/// }
+///
+/// let input = Sum::parse("1\n5\n3");
+/// assert_eq!(Sum::part1(&input).to_string(), "9");
+/// assert_eq!(Sum::part2(&input).to_string(), "5");
+/// ```
+pub trait Solution {
+    /// Parsed representation of the puzzle input.
+    type Input;
+
+    fn parse(input: &str) -> Self::Input;
+    fn part1(input: &Self::Input) -> impl Display;
+    fn part2(input: &Self::Input) -> impl Display;
+}
+
+/// One of the two parts of a puzzle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    /// Returns an array of both parts.
+    pub fn all() -> [Self; 2] {
+        [Self::One, Self::Two]
+    }
+
+    /// Returns the number of the part, 1 or 2.
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Self::One),
+            "2" => Ok(Self::Two),
+            _ => Err(format!("invalid part '{s}', expected 1 or 2")),
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// Type erased [`Solution`] of a specific day, so that solutions of
+/// different days can be registered in a single list.
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub day: u8,
+    parse: fn(&str) -> Box<dyn Any>,
+    part1: fn(&dyn Any) -> String,
+    part2: fn(&dyn Any) -> String,
+}
+
+impl Day {
+    /// Creates a new entry for the solution `S` of day `day`.
+    pub fn new<S>(day: u8) -> Self
+    where
+        S: Solution,
+        S::Input: 'static,
+    {
+        Self {
+            day,
+            parse: |input| Box::new(S::parse(input)),
+            part1: |input| S::part1(downcast::<S>(input)).to_string(),
+            part2: |input| S::part2(downcast::<S>(input)).to_string(),
+        }
+    }
+
+    /// Parses the given puzzle input.
+    pub fn parse(&self, input: &str) -> Box<dyn Any> {
+        (self.parse)(input)
+    }
+
+    /// Solves the given part for an input returned by [`Day::parse`].
+    pub fn solve(&self, input: &dyn Any, part: Part) -> String {
+        match part {
+            Part::One => (self.part1)(input),
+            Part::Two => (self.part2)(input),
+        }
+    }
+}
+
+impl fmt::Debug for Day {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Day").field("day", &self.day).finish()
+    }
+}
+
+fn downcast<S>(input: &dyn Any) -> &S::Input
+where
+    S: Solution,
+    S::Input: 'static,
+{
+    input
+        .downcast_ref()
+        .expect("input has been parsed by the same solution")
+}
@@ -0,0 +1,329 @@
+//! Interpreter for instructions hidden in noisy text, like `mul(2,4)`
+//! between random characters.
+//!
+//! Instructions are registered on a [`Vm`] by name and argument types.
+//! [`Vm::tokenize`] finds all well-formed instructions in the input, which
+//! [`Vm::execute`] then runs against some state. Besides regular
+//! instructions, there are toggles which enable or disable the execution of
+//! all following regular instructions.
+//!
+//! # Example
+//! ```
+//! use lib::vm::{ArgType, Vm};
+//!
+//! let vm = Vm::new()
+//!     .instruction("add", &[ArgType::Int, ArgType::Int], |sum: &mut i64, args| {
+//!         *sum += args[0].int() + args[1].int()
+//!     })
+//!     .disable("stop")
+//!     .enable("go");
+//!
+//! let tokens = vm.tokenize("add(1,2)-stop()add(3,4)*go()!add(5,-6)add(7)");
+//! assert_eq!(tokens.len(), 5);
+//!
+//! let mut sum = 0;
+//! vm.execute(&tokens, &mut sum);
+//! assert_eq!(sum, 3 - 1);
+//! ```
+
+use crate::combinator::{number, scan, spanned, tag, Cursor, PResult};
+
+/// Type of an instruction argument.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArgType {
+    /// A decimal integer with an optional leading `-`.
+    Int,
+    /// A decimal integer without sign of one up to `max_digits` digits.
+    UInt { max_digits: usize },
+    /// A non-empty sequence of ASCII letters, digits and underscores.
+    Word,
+}
+
+impl ArgType {
+    fn parse(self, cur: &mut Cursor) -> PResult<Value> {
+        match self {
+            Self::Int => number(cur).map(Value::Int),
+            Self::UInt { max_digits } => {
+                let len = cur.rest().bytes().take_while(u8::is_ascii_digit).count();
+                let expected = || format!("a number of 1 to {max_digits} digits");
+                if len == 0 || len > max_digits {
+                    return Err(cur.error(expected()));
+                }
+                let n = cur.rest()[..len]
+                    .parse()
+                    .map_err(|_| cur.error(expected()))?;
+                cur.advance(len);
+                Ok(Value::Int(n))
+            }
+            Self::Word => {
+                let len = cur
+                    .rest()
+                    .bytes()
+                    .take_while(|b| b.is_ascii_alphanumeric() || *b == b'_')
+                    .count();
+                if len == 0 {
+                    return Err(cur.error("a word"));
+                }
+                Ok(Value::Word(cur.advance(len).to_owned()))
+            }
+        }
+    }
+}
+
+/// Value of an instruction argument.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Int(i64),
+    Word(String),
+}
+
+impl Value {
+    /// Returns the value of an [`ArgType::Int`] or [`ArgType::UInt`]
+    /// argument.
+    ///
+    /// # Panics
+    /// Panics if the value is not an integer.
+    pub fn int(&self) -> i64 {
+        match self {
+            Self::Int(n) => *n,
+            Self::Word(w) => panic!("expected an integer argument, got '{w}'"),
+        }
+    }
+
+    /// Returns the value of an [`ArgType::Word`] argument.
+    ///
+    /// # Panics
+    /// Panics if the value is not a word.
+    pub fn word(&self) -> &str {
+        match self {
+            Self::Word(w) => w,
+            Self::Int(n) => panic!("expected a word argument, got {n}"),
+        }
+    }
+}
+
+/// An instruction found in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    /// Index of the instruction in the order of registration.
+    pub op: usize,
+    pub args: Vec<Value>,
+    /// Byte offset of the instruction in the input.
+    pub offset: usize,
+}
+
+/// Executes a regular instruction with its arguments.
+type Exec<S> = Box<dyn Fn(&mut S, &[Value])>;
+
+enum Kind<S> {
+    Exec(Exec<S>),
+    Enable,
+    Disable,
+}
+
+struct Op<S> {
+    name: String,
+    args: Vec<ArgType>,
+    kind: Kind<S>,
+}
+
+/// Interpreter for instructions of the form `name(arg,arg,...)` operating
+/// on a state of type `S`.
+pub struct Vm<S> {
+    ops: Vec<Op<S>>,
+    toggles: bool,
+}
+
+impl<S> Default for Vm<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> Vm<S> {
+    pub fn new() -> Self {
+        Self {
+            ops: vec![],
+            toggles: true,
+        }
+    }
+
+    /// Registers an instruction with the given argument types, which calls
+    /// `exec` with the state and the arguments while execution is enabled.
+    pub fn instruction(
+        self,
+        name: &str,
+        args: &[ArgType],
+        exec: impl Fn(&mut S, &[Value]) + 'static,
+    ) -> Self {
+        self.op(name, args, Kind::Exec(Box::new(exec)))
+    }
+
+    /// Registers an instruction without arguments, which enables the
+    /// execution of the following instructions.
+    pub fn enable(self, name: &str) -> Self {
+        self.op(name, &[], Kind::Enable)
+    }
+
+    /// Registers an instruction without arguments, which disables the
+    /// execution of the following instructions until the next enabling
+    /// instruction.
+    pub fn disable(self, name: &str) -> Self {
+        self.op(name, &[], Kind::Disable)
+    }
+
+    /// Makes [`Vm::execute`] ignore enabling and disabling instructions, so
+    /// that all instructions are executed.
+    pub fn ignore_toggles(mut self) -> Self {
+        self.toggles = false;
+        self
+    }
+
+    fn op(mut self, name: &str, args: &[ArgType], kind: Kind<S>) -> Self {
+        self.ops.push(Op {
+            name: name.to_owned(),
+            args: args.to_vec(),
+            kind,
+        });
+        self
+    }
+
+    /// Returns the name of the instruction of a token.
+    pub fn name(&self, token: &Token) -> &str {
+        &self.ops[token.op].name
+    }
+
+    /// Returns all well-formed instructions in `input`, ignoring any text
+    /// in between.
+    ///
+    /// # Example
+    /// ```
+    /// # use lib::vm::{ArgType, Token, Value, Vm};
+    /// let vm = Vm::<()>::new()
+    ///     .instruction("set", &[ArgType::Word, ArgType::Int], |_, _| {})
+    ///     .enable("on");
+    ///
+    /// let tokens = vm.tokenize("set(a,1) set(b) xon()");
+    /// assert_eq!(
+    ///     tokens,
+    ///     [
+    ///         Token { op: 0, args: vec![Value::Word("a".into()), Value::Int(1)], offset: 0 },
+    ///         Token { op: 1, args: vec![], offset: 17 },
+    ///     ]
+    /// );
+    /// assert_eq!(vm.name(&tokens[1]), "on");
+    /// ```
+    pub fn tokenize(&self, input: &str) -> Vec<Token> {
+        scan(input, &spanned(|cur: &mut Cursor| self.token(cur)))
+            .into_iter()
+            .map(|(offset, (op, args))| Token { op, args, offset })
+            .collect()
+    }
+
+    /// Parses any of the registered instructions at the cursor.
+    fn token(&self, cur: &mut Cursor) -> PResult<(usize, Vec<Value>)> {
+        for (i, op) in self.ops.iter().enumerate() {
+            let args = cur.attempt(&|cur: &mut Cursor| {
+                tag(&op.name)(cur)?;
+                tag("(")(cur)?;
+                let mut args = vec![];
+                for (j, arg) in op.args.iter().enumerate() {
+                    if j > 0 {
+                        tag(",")(cur)?;
+                    }
+                    args.push(arg.parse(cur)?);
+                }
+                tag(")")(cur)?;
+                Ok(args)
+            });
+            if let Ok(args) = args {
+                return Ok((i, args));
+            }
+        }
+        Err(cur.error("an instruction"))
+    }
+
+    /// Executes `tokens` in order against `state`. Execution starts
+    /// enabled.
+    pub fn execute(&self, tokens: &[Token], state: &mut S) {
+        let mut enabled = true;
+        for token in tokens {
+            match &self.ops[token.op].kind {
+                Kind::Exec(exec) if enabled || !self.toggles => exec(state, &token.args),
+                Kind::Exec(_) => {}
+                Kind::Enable => enabled = true,
+                Kind::Disable => enabled = false,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_tokenize() {
+        let vm = Vm::<()>::new()
+            .instruction("add", &[ArgType::Int, ArgType::Int], |_, _| {})
+            .enable("do")
+            .disable("don't");
+
+        let tokens = vm.tokenize("add(1,2)add(3,4]don't()do(x)üadd(add(-5,6)do()");
+        let found: Vec<_> = tokens
+            .iter()
+            .map(|t| (vm.name(t), t.args.clone(), t.offset))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("add", vec![Value::Int(1), Value::Int(2)], 0),
+                ("don't", vec![], 16),
+                ("add", vec![Value::Int(-5), Value::Int(6)], 34),
+                ("do", vec![], 43),
+            ]
+        );
+
+        assert!(vm.tokenize("").is_empty());
+        assert!(vm.tokenize("add (1,2) add(1, 2) add(1,2,3)").is_empty());
+    }
+
+    #[test]
+    fn test_unsigned_args() {
+        let vm = Vm::<()>::new().instruction(
+            "mul",
+            &[
+                ArgType::UInt { max_digits: 3 },
+                ArgType::UInt { max_digits: 3 },
+            ],
+            |_, _| {},
+        );
+
+        let tokens = vm.tokenize("mul(-5,6)mul(1234,5)mul(5,1234)mul(+5,6)mul(,6)mul(123,45)");
+        let found: Vec<_> = tokens.iter().map(|t| (t.args.clone(), t.offset)).collect();
+        assert_eq!(found, [(vec![Value::Int(123), Value::Int(45)], 47)]);
+    }
+
+    #[test]
+    fn test_execute() {
+        let vm = Vm::new()
+            .instruction("push", &[ArgType::Word], |stack: &mut Vec<String>, args| {
+                stack.push(args[0].word().to_owned())
+            })
+            .instruction("pop", &[], |stack, _| {
+                stack.pop();
+            })
+            .disable("off")
+            .enable("on");
+        let tokens = vm.tokenize("push(a)off()push(b)on()pop()push(c)off()push(d)");
+
+        let mut stack = vec![];
+        vm.execute(&tokens, &mut stack);
+        assert_eq!(stack, ["c"]);
+
+        let vm = vm.ignore_toggles();
+        let mut stack = vec![];
+        vm.execute(&tokens, &mut stack);
+        assert_eq!(stack, ["a", "c", "d"]);
+    }
+}